@@ -1,14 +1,20 @@
 use canopie_macros::{ThemeConfig, theme_defaults};
+use canopie_themes_base::{
+    config::{ConfigMigration, GetThemeConfig, resolve_config, resolve_fields},
+    content::RenderMode,
+    data::{RenderSite, SiteData},
+    diagnostics::Diagnostics,
+    page::{PageContext, RenderOutcome, finish_response, render_page_content},
+    registry::ThemeRegistry,
+    schema::with_dropdown_options,
+};
 use canopie_utils::themes;
 use canopie_utils::{
-    components::{Asset, AssetOptions, Formatter, build_components},
-    db::PgPool,
+    components::{Asset, AssetOptions, Formatter},
     header::Header,
-    models::{MenuItem, Website},
-    renderer::{PageResponse, PageResult},
-    theme_utils::{get_menus, get_page, get_page_from_id},
+    models::{Page, Website},
+    renderer::PageResponse,
     themes::{GetThemeOverview, PageThemeOverview, ThemeOverview, ThemeRestriction, ThemeSchema},
-    types::tiptap::{empty_tiptap_node, to_tiptap_node},
 };
 
 #[cfg(feature = "embed")]
@@ -20,17 +26,16 @@ use canopie_utils::{
 use include_dir::Dir;
 use serde::{Deserialize, Serialize};
 
+use crate::nav::build_nav_items;
+use crate::options::lowercase_dropdown_values;
 use crate::templates::{not_found::atlas_not_found, page::page_layout};
 
-mod templates;
+pub use crate::nav::NavItem;
+pub use crate::options::{HeaderStyle, HeroLayout, LayoutWidth};
 
-#[derive(Debug, Clone)]
-pub struct NavItem {
-    pub label: String,
-    pub href: String,
-    pub active: bool,
-    pub external: bool,
-}
+mod nav;
+mod options;
+mod templates;
 
 #[theme_defaults]
 #[derive(Serialize, Deserialize, Debug, ThemeConfig)]
@@ -56,16 +61,36 @@ pub struct AtlasThemeConfig {
     #[theme(interface = "FileImage")]
     pub hero_image: Option<String>,
     #[theme(interface = "SelectDropdown", default = "wide", width = "Half")]
-    pub layout_width: String,
+    pub layout_width: LayoutWidth,
     #[theme(interface = "SelectDropdown", default = "glass", width = "Half")]
-    pub header_style: String,
-    #[theme(interface = "SelectColor", default = "#f8fafc", width = "Half")]
+    pub header_style: HeaderStyle,
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#f8fafc",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
     pub surface_color: String,
-    #[theme(interface = "SelectColor", default = "#0f172a", width = "Half")]
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#0f172a",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
     pub text_color: String,
-    #[theme(interface = "SelectColor", default = "#0ea5e9", width = "Half")]
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#0ea5e9",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
     pub accent_color: String,
-    #[theme(interface = "SelectColor", default = "#64748b", width = "Half")]
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#64748b",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
     pub muted_color: String,
     #[theme(interface = "Checkbox", default = true, width = "Half")]
     pub show_footer: bool,
@@ -95,8 +120,8 @@ impl Default for AtlasThemeConfig {
             hero_subtitle: "Thoughtful pages with clear hierarchy and generous spacing."
                 .to_string(),
             hero_image: None,
-            layout_width: "wide".to_string(),
-            header_style: "glass".to_string(),
+            layout_width: LayoutWidth::default(),
+            header_style: HeaderStyle::default(),
             surface_color: "#f8fafc".to_string(),
             text_color: "#0f172a".to_string(),
             accent_color: "#0ea5e9".to_string(),
@@ -121,7 +146,68 @@ pub struct AtlasPageConfig {
     #[theme(interface = "FileImage")]
     pub hero_image: Option<String>,
     #[theme(interface = "SelectDropdown", default = "split", width = "Half")]
-    pub hero_layout: String,
+    pub hero_layout: HeroLayout,
+}
+
+impl Default for AtlasPageConfig {
+    fn default() -> Self {
+        Self {
+            show_hero: true,
+            hero_overline: String::new(),
+            hero_subtitle: String::new(),
+            hero_image: None,
+            hero_layout: HeroLayout::default(),
+        }
+    }
+}
+
+/// Hero settings for a single page: the page's own options, with anything it
+/// leaves empty taken from the site-wide theme config.
+#[derive(Debug, Clone)]
+pub struct HeroSettings {
+    pub show: bool,
+    pub overline: String,
+    pub subtitle: String,
+    pub image: Option<String>,
+    pub layout: HeroLayout,
+}
+
+impl HeroSettings {
+    /// The site-wide hero, used where there is no page to take options from.
+    pub fn from_site(config: &AtlasThemeConfig) -> Self {
+        Self {
+            show: config.show_page_hero,
+            overline: config.hero_overline.clone(),
+            subtitle: config.hero_subtitle.clone(),
+            image: config.hero_image.clone(),
+            layout: HeroLayout::default(),
+        }
+    }
+
+    pub fn for_page(config: &AtlasThemeConfig, page: &Page) -> Self {
+        let site = Self::from_site(config);
+        let page_config: AtlasPageConfig = resolve_fields(page.options.clone());
+
+        Self {
+            show: if page.options.get("show_hero").is_some() {
+                page_config.show_hero
+            } else {
+                site.show
+            },
+            overline: non_empty_or(page_config.hero_overline, site.overline),
+            subtitle: non_empty_or(page_config.hero_subtitle, site.subtitle),
+            image: page_config.hero_image.or(site.image),
+            layout: page_config.hero_layout,
+        }
+    }
+}
+
+fn non_empty_or(value: String, fallback: String) -> String {
+    if value.trim().is_empty() {
+        fallback
+    } else {
+        value
+    }
 }
 
 pub struct ThemeAtlas {}
@@ -133,16 +219,42 @@ impl GetThemeOverview for ThemeAtlas {
             name: "Atlas".to_string(),
             description: Some("A flexible, multi-page theme with strong hierarchy.".to_string()),
             restricted: ThemeRestriction::None,
-            config: AtlasThemeConfig::schema(),
+            config: with_dropdown_options::<HeaderStyle>(
+                with_dropdown_options::<LayoutWidth>(AtlasThemeConfig::schema(), "layout_width"),
+                "header_style",
+            ),
             page_themes: vec![PageThemeOverview {
                 name: "Atlas Page".to_string(),
-                config: AtlasPageConfig::schema(),
+                config: with_dropdown_options::<HeroLayout>(
+                    AtlasPageConfig::schema(),
+                    "hero_layout",
+                ),
                 default: true,
             }],
         }
     }
 }
 
+impl GetThemeConfig for ThemeAtlas {
+    type Config = AtlasThemeConfig;
+
+    fn migrations() -> Vec<ConfigMigration> {
+        vec![lowercase_dropdown_values]
+    }
+}
+
+impl RenderSite for ThemeAtlas {
+    fn render_site(
+        data: &dyn SiteData,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+        mode: RenderMode,
+    ) -> RenderOutcome {
+        atlas(data, headers, website, path, mode)
+    }
+}
+
 #[cfg(feature = "embed")]
 impl ThemeResource for ThemeAtlas {
     fn get_theme_resources() -> Dir<'static> {
@@ -166,7 +278,9 @@ impl GetThemeRenderer for ThemeAtlas {
     fn get_theme_renderer() -> ThemeRenderer {
         ThemeRenderer {
             name: Self::get_theme_overview().id,
-            build_content: atlas,
+            build_content: |pool, headers, website, path| {
+                atlas(pool, headers, website, path, RenderMode::Live).into()
+            },
         }
     }
 }
@@ -175,45 +289,60 @@ pub fn get_theme_overview() -> ThemeOverview {
     ThemeAtlas::get_theme_overview()
 }
 
-pub fn atlas(pool: &PgPool, headers: &mut Header, website: Website, path: &str) -> PageResult {
+#[cfg(not(feature = "embed"))]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry.register::<ThemeAtlas>()
+}
+
+#[cfg(feature = "embed")]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry
+        .register::<ThemeAtlas>()
+        .with_renderer::<ThemeAtlas>()
+        .with_resources::<ThemeAtlas>()
+}
+
+pub fn atlas<D: SiteData + ?Sized>(
+    data: &D,
+    headers: &mut Header,
+    website: Website,
+    path: &str,
+    mode: RenderMode,
+) -> RenderOutcome {
     let theme_config = resolve_theme_config(&website);
+    let diagnostics = Diagnostics::for_mode(mode);
 
     let formatter = Formatter::default(Some(website.clone()));
     formatter.add_asset(Asset::Style(AssetOptions::new("tailwind")));
     formatter.add_asset(Asset::Style(AssetOptions::new("atlas")));
 
-    let menus = get_menus(pool, &website.id);
-    let main_menu = menus.iter().find(|menu| menu.0.name == "Main");
-    let nav_items = main_menu
-        .map(|menu| build_nav_items(pool, &menu.1, path))
+    let context = PageContext::load(data, &website.id, path);
+    let nav_items = context
+        .main_menu()
+        .map(|menu| {
+            build_nav_items(
+                &context.menus,
+                &menu.1,
+                &context.menu_pages,
+                &context.labels,
+                path,
+            )
+        })
         .unwrap_or_default();
 
-    let page_result = get_page(pool, &website.id, path);
-
-    if page_result.is_none() || page_result.as_ref().unwrap().page_content.is_none() {
+    let Some((page, page_content)) = context.page else {
         let response = atlas_not_found(&theme_config, &website, &nav_items, headers, formatter);
-        let assets = response.formatter.collect_assets(Some("atlas"));
-        headers.add_assets(assets);
-        headers.set_title(response.title.as_str());
-        return response.result;
-    }
-
-    let page_data = page_result.unwrap();
-    let page = page_data.page;
-    let page_content = page_data.page_content.unwrap();
-
-    let content = match to_tiptap_node(page_content.content) {
-        Ok(content) => content,
-        Err(_error) => empty_tiptap_node(Some("Error parsing content")),
+        return finish_response(response, "atlas", headers, &diagnostics);
     };
 
-    let (components, content_formatter) = build_components(content, None, Some(formatter.clone()));
-    formatter.add_assets_from(&content_formatter);
+    let components = render_page_content(page.id.as_str(), page_content, &formatter, &diagnostics);
+
+    let hero = HeroSettings::for_page(&theme_config, &page);
 
     let body = page_layout(
         &theme_config,
+        &hero,
         &website,
-        path,
         page.title.as_str(),
         &nav_items,
         components,
@@ -221,69 +350,9 @@ pub fn atlas(pool: &PgPool, headers: &mut Header, website: Website, path: &str)
     );
 
     let response = PageResponse::new(page.title, body, formatter);
-    let assets = response.formatter.collect_assets(Some("atlas"));
-    headers.add_assets(assets);
-    headers.set_title(response.title.as_str());
-
-    response.result
+    finish_response(response, "atlas", headers, &diagnostics)
 }
 
 fn resolve_theme_config(website: &Website) -> AtlasThemeConfig {
-    if let Some(value) = website.theme_config.clone() {
-        AtlasThemeConfig::from_json_value(value).unwrap_or_default()
-    } else {
-        AtlasThemeConfig::default()
-    }
-}
-
-fn build_nav_items(pool: &PgPool, menu_items: &[MenuItem], current_path: &str) -> Vec<NavItem> {
-    let mut sorted = menu_items.to_vec();
-    sorted.sort_by_key(|item| item.order);
-
-    sorted
-        .iter()
-        .filter_map(|item| {
-            if let Some(url) = item.url.clone() {
-                return Some(NavItem {
-                    label: url.clone(),
-                    href: url,
-                    active: false,
-                    external: true,
-                });
-            }
-
-            if let Some(page_id) = &item.page_id {
-                let page_fetch = get_page_from_id(pool, page_id);
-                if let Some((page, _page_content)) = page_fetch {
-                    let slug = if page.slug.is_empty() {
-                        "/".to_string()
-                    } else {
-                        format!("/{}", page.slug)
-                    };
-                    let active = normalize_path(current_path) == normalize_path(&slug);
-                    return Some(NavItem {
-                        label: page.title,
-                        href: slug,
-                        active,
-                        external: false,
-                    });
-                }
-            }
-
-            None
-        })
-        .collect()
-}
-
-fn normalize_path(path: &str) -> String {
-    let trimmed = path.trim();
-    if trimmed.is_empty() {
-        return "/".to_string();
-    }
-    let trimmed = trimmed.trim_end_matches('/');
-    if trimmed.is_empty() {
-        "/".to_string()
-    } else {
-        trimmed.to_string()
-    }
+    resolve_config::<ThemeAtlas>(website.theme_config.as_ref())
 }
@@ -0,0 +1,266 @@
+use canopie_themes_base::menu::{MenuLabels, MenuPages, pretty_url};
+use canopie_utils::models::{Menu, MenuItem};
+
+/// Submenus nested deeper than this are dropped, which also stops menus that
+/// reference each other from recursing forever.
+const MAX_NAV_DEPTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct NavItem {
+    pub label: String,
+    /// `None` for items that only group a submenu.
+    pub href: Option<String>,
+    /// The item links to the page being rendered.
+    pub current: bool,
+    /// The item or any of its children links to the page being rendered.
+    pub active: bool,
+    pub external: bool,
+    pub children: Vec<NavItem>,
+}
+
+/// Builds the navigation tree for `menu`.
+///
+/// Labels come from `labels`, falling back to the page title for page links
+/// and a shortened URL for external links. Children come from items whose
+/// `parent_id` points at the item, followed by the top-level items of the
+/// menu its `submenu_id` points at.
+pub(crate) fn build_nav_items(
+    menus: &[(Menu, Vec<MenuItem>)],
+    menu_items: &[MenuItem],
+    pages: &MenuPages,
+    labels: &MenuLabels,
+    current_path: &str,
+) -> Vec<NavItem> {
+    let builder = NavBuilder {
+        menus,
+        pages,
+        labels,
+        current_path: normalize_path(current_path),
+    };
+
+    builder.level(menu_items, None, 0)
+}
+
+struct NavBuilder<'a> {
+    menus: &'a [(Menu, Vec<MenuItem>)],
+    pages: &'a MenuPages,
+    labels: &'a MenuLabels,
+    current_path: String,
+}
+
+impl NavBuilder<'_> {
+    fn level(
+        &self,
+        menu_items: &[MenuItem],
+        parent_id: Option<&str>,
+        depth: usize,
+    ) -> Vec<NavItem> {
+        if depth > MAX_NAV_DEPTH {
+            return vec![];
+        }
+
+        let mut level: Vec<&MenuItem> = menu_items
+            .iter()
+            .filter(|item| item.parent_id.as_deref() == parent_id)
+            .collect();
+        level.sort_by_key(|item| item.order);
+
+        level
+            .into_iter()
+            .filter_map(|item| self.item(menu_items, item, depth))
+            .collect()
+    }
+
+    fn item(&self, menu_items: &[MenuItem], item: &MenuItem, depth: usize) -> Option<NavItem> {
+        let mut children = self.level(menu_items, Some(item.id.as_str()), depth + 1);
+
+        let submenu = item
+            .submenu_id
+            .as_ref()
+            .and_then(|submenu_id| self.menus.iter().find(|(menu, _)| &menu.id == submenu_id));
+        if let Some((_submenu, submenu_items)) = submenu {
+            children.extend(self.level(submenu_items, None, depth + 1));
+        }
+
+        let child_active = children.iter().any(|child| child.active);
+        let label = self.labels.get(&item.id).map(str::to_string);
+
+        if let Some(url) = item.url.clone() {
+            return Some(NavItem {
+                label: label.unwrap_or_else(|| pretty_url(&url)),
+                href: Some(url),
+                current: false,
+                active: child_active,
+                external: true,
+                children,
+            });
+        }
+
+        if let Some((page, _page_content)) = item
+            .page_id
+            .as_ref()
+            .and_then(|page_id| self.pages.active(page_id))
+        {
+            let slug = if page.slug.is_empty() {
+                "/".to_string()
+            } else {
+                format!("/{}", page.slug)
+            };
+            let current = self.current_path == normalize_path(&slug);
+            return Some(NavItem {
+                label: label.unwrap_or_else(|| page.title.clone()),
+                href: Some(slug),
+                current,
+                active: current || child_active,
+                external: false,
+                children,
+            });
+        }
+
+        let (submenu, _) = submenu?;
+        if children.is_empty() {
+            return None;
+        }
+
+        Some(NavItem {
+            label: label.unwrap_or_else(|| submenu.name.clone()),
+            href: None,
+            current: false,
+            active: child_active,
+            external: false,
+            children,
+        })
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return "/".to_string();
+    }
+    let trimmed = trimmed.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopie_themes_base::data::{
+        MemorySiteData, SiteData,
+        test_support::{menu, menu_item, page},
+    };
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn item(id: &str, menu_id: &str, parent_id: Option<&str>, page_id: Option<&str>) -> Value {
+        menu_item(id, menu_id, parent_id, page_id, 1)
+    }
+
+    /// A Main menu nested five levels deep through `parent_id`, plus an item
+    /// opening a submenu whose only item opens the Main menu again.
+    fn site() -> MemorySiteData {
+        let mut more = item("item-more", "menu-main", None, None);
+        more["submenu_id"] = json!("menu-more");
+        let mut back = item("item-back", "menu-more", None, None);
+        back["submenu_id"] = json!("menu-main");
+
+        serde_json::from_value(json!({
+            "menus": [menu("menu-main", "Main", 1), menu("menu-more", "More", 1)],
+            "menu_items": [
+                item("item-about", "menu-main", None, Some("page-about")),
+                item("item-team", "menu-main", Some("item-about"), Some("page-team")),
+                item("item-people", "menu-main", Some("item-team"), Some("page-people")),
+                item("item-alex", "menu-main", Some("item-people"), Some("page-alex")),
+                item("item-notes", "menu-main", Some("item-alex"), Some("page-notes")),
+                more,
+                back,
+            ],
+            "pages": [
+                page("page-about", "about", 1),
+                page("page-team", "about/team", 1),
+                page("page-people", "about/team/people", 1),
+                page("page-alex", "about/team/people/alex", 1),
+                page("page-notes", "about/team/people/alex/notes", 1),
+            ],
+        }))
+        .unwrap()
+    }
+
+    fn nav(current_path: &str) -> Vec<NavItem> {
+        let site = site();
+        let menus = site.menus("site");
+        let pages = MenuPages::load(&site, &menus);
+        let main_items = &menus.iter().find(|menu| menu.0.name == "Main").unwrap().1;
+
+        build_nav_items(
+            &menus,
+            main_items,
+            &pages,
+            &MenuLabels::default(),
+            current_path,
+        )
+    }
+
+    fn find<'a>(items: &'a [NavItem], label: &str) -> &'a NavItem {
+        items
+            .iter()
+            .find(|item| item.label == label)
+            .unwrap_or_else(|| panic!("no nav item {}", label))
+    }
+
+    fn depth(items: &[NavItem]) -> usize {
+        items
+            .iter()
+            .map(|item| 1 + depth(&item.children))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn children_below_the_maximum_depth_are_dropped() {
+        let nav = nav("/");
+
+        let about = find(&nav, "page-about");
+        let team = find(&about.children, "page-team");
+        let people = find(&team.children, "page-people");
+        let alex = find(&people.children, "page-alex");
+
+        assert!(alex.children.is_empty());
+        assert_eq!(depth(&nav), MAX_NAV_DEPTH + 1);
+    }
+
+    #[test]
+    fn menus_opening_each_other_stop_at_the_maximum_depth() {
+        let nav = nav("/");
+
+        let more = find(&nav, "More");
+        assert_eq!(more.href, None);
+        assert!(depth(&more.children) <= MAX_NAV_DEPTH);
+    }
+
+    #[test]
+    fn ancestors_of_the_current_page_are_active() {
+        let nav = nav("/about/team/people/");
+
+        let about = find(&nav, "page-about");
+        let team = find(&about.children, "page-team");
+        let people = find(&team.children, "page-people");
+
+        assert!(about.active && !about.current);
+        assert!(team.active && !team.current);
+        assert!(people.active && people.current);
+        assert!(!find(&people.children, "page-alex").active);
+        assert!(!find(&nav, "More").active);
+    }
+
+    #[test]
+    fn nothing_is_active_off_the_menu() {
+        let nav = nav("/somewhere-else");
+
+        assert!(nav.iter().all(|item| !item.active && !item.current));
+    }
+}
@@ -0,0 +1,82 @@
+use canopie_themes_base::schema::DropdownOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How the hero at the top of an Atlas page is laid out.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HeroLayout {
+    /// Copy on the left, image on the right. Without an image it is laid out
+    /// like [`HeroLayout::Centered`].
+    #[default]
+    Split,
+    /// Centred copy with the image below it.
+    Centered,
+    /// Copy over the hero image, which fills the full width.
+    FullBleed,
+    /// The page title only.
+    Compact,
+    /// No hero at all.
+    None,
+}
+
+impl DropdownOptions for HeroLayout {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("split", "Split"),
+            ("centered", "Centred"),
+            ("full_bleed", "Full-bleed image"),
+            ("compact", "Compact (title only)"),
+            ("none", "No hero"),
+        ]
+    }
+}
+
+/// How wide the header, hero, content and footer containers are.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutWidth {
+    Narrow,
+    #[default]
+    Wide,
+    Full,
+}
+
+impl DropdownOptions for LayoutWidth {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("narrow", "Narrow"),
+            ("wide", "Wide"),
+            ("full", "Full width"),
+        ]
+    }
+}
+
+/// How the site header is drawn over the page.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderStyle {
+    Solid,
+    /// Translucent and blurred over the content below it.
+    #[default]
+    Glass,
+}
+
+impl DropdownOptions for HeaderStyle {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        vec![("solid", "Solid"), ("glass", "Glass")]
+    }
+}
+
+/// Config migration 1. `layout_width` and `header_style` were free strings
+/// before they became enums, so values saved as e.g. `"Wide"` are trimmed and
+/// lowercased to keep deserialising. Anything still unknown is left for
+/// validation to report.
+pub(crate) fn lowercase_dropdown_values(mut value: Value) -> Value {
+    for field in ["layout_width", "header_style"] {
+        if let Some(Value::String(option)) = value.get_mut(field) {
+            *option = option.trim().to_lowercase();
+        }
+    }
+    value
+}
@@ -1,13 +1,10 @@
 use canopie_utils::{
-    components::Formatter,
-    header::Header,
-    models::Website,
-    renderer::PageResponse,
+    components::Formatter, header::Header, models::Website, renderer::PageResponse,
 };
 use maud::html;
 
-use crate::{AtlasThemeConfig, NavItem};
 use crate::templates::page::page_layout;
+use crate::{AtlasThemeConfig, HeroSettings, NavItem};
 
 pub fn atlas_not_found(
     config: &AtlasThemeConfig,
@@ -30,8 +27,8 @@ pub fn atlas_not_found(
 
     let body = page_layout(
         config,
+        &HeroSettings::from_site(config),
         website,
-        "/404",
         "Page not found",
         nav_items,
         content,
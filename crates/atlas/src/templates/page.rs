@@ -1,12 +1,46 @@
+use canopie_themes_base::css::background_image;
 use canopie_utils::header::Header;
-use maud::{Markup, html};
+use maud::{Markup, PreEscaped, html};
 
-use crate::{AtlasThemeConfig, NavItem};
+use crate::{AtlasThemeConfig, HeaderStyle, HeroLayout, HeroSettings, LayoutWidth, NavItem};
+
+/// Dropdowns are `<details>` elements, so they open with the keyboard and
+/// without JavaScript. This script only mirrors the open state into
+/// `aria-expanded` and closes open dropdowns on Escape or an outside click.
+const NAV_DROPDOWN_SCRIPT: &str = r#"(function () {
+  function sync(dropdown) {
+    var summary = dropdown.querySelector("summary");
+    if (summary) summary.setAttribute("aria-expanded", dropdown.open ? "true" : "false");
+  }
+  function closeAll(except) {
+    document.querySelectorAll(".atlas-nav-dropdown[open]").forEach(function (dropdown) {
+      if (dropdown !== except && !dropdown.contains(except)) dropdown.open = false;
+    });
+  }
+  document.addEventListener("DOMContentLoaded", function () {
+    document.querySelectorAll(".atlas-nav-dropdown").forEach(sync);
+  });
+  document.addEventListener("toggle", function (event) {
+    var dropdown = event.target;
+    if (!dropdown.matches || !dropdown.matches(".atlas-nav-dropdown")) return;
+    sync(dropdown);
+    if (dropdown.open) closeAll(dropdown);
+  }, true);
+  document.addEventListener("click", function (event) {
+    if (!event.target.closest(".atlas-nav-dropdown")) closeAll(null);
+  });
+  document.addEventListener("keydown", function (event) {
+    if (event.key !== "Escape") return;
+    var open = event.target.closest && event.target.closest(".atlas-nav-dropdown[open]");
+    closeAll(null);
+    if (open) open.querySelector("summary").focus();
+  });
+})();"#;
 
 pub fn page_layout(
     config: &AtlasThemeConfig,
+    hero: &HeroSettings,
     website: &canopie_utils::models::Website,
-    path: &str,
     page_title: &str,
     nav_items: &[NavItem],
     content: Markup,
@@ -17,9 +51,14 @@ pub fn page_layout(
         link rel="preconnect" href="https://fonts.gstatic.com";
         link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=DM+Sans:wght@400;500;600;700&display=swap";
     });
+    if nav_items.iter().any(|item| !item.children.is_empty()) {
+        headers.add_asset(html! {
+            script { (PreEscaped(NAV_DROPDOWN_SCRIPT)) }
+        });
+    }
 
-    let container_class = container_width_class(config.layout_width.as_str());
-    let header_class = header_style_class(config.header_style.as_str());
+    let container_class = container_width_class(config.layout_width);
+    let header_class = header_style_class(config.header_style);
     let style_vars = format!(
         "--atlas-surface: {}; --atlas-text: {}; --atlas-accent: {}; --atlas-muted: {};",
         config.surface_color, config.text_color, config.accent_color, config.muted_color
@@ -41,12 +80,10 @@ pub fn page_layout(
                         }
                         span { (site_title) }
                     }
-                    nav class="atlas-nav" {
-                        @for item in nav_items {
-                            @if item.external {
-                                a class=(nav_link_class(item.active)) href=(item.href) target="_blank" rel="noopener noreferrer" { (item.label) }
-                            } @else {
-                                a class=(nav_link_class(item.active)) href=(item.href) { (item.label) }
+                    nav class="atlas-nav" aria-label="Main" {
+                        ul class="atlas-nav-list" {
+                            @for item in nav_items {
+                                (nav_entry(item))
                             }
                         }
                     }
@@ -56,55 +93,8 @@ pub fn page_layout(
                 }
             }
             main class="atlas-main" {
-                @if config.show_page_hero {
-                    section class="atlas-hero atlas-border" {
-                        div class=(format!("atlas-container atlas-hero-inner {}", container_class)) {
-                            div class="atlas-hero-content" {
-                                @if !config.hero_overline.trim().is_empty() {
-                                    span class="atlas-pill" { (config.hero_overline) }
-                                }
-                                h1 class="atlas-hero-title" { (page_title) }
-                                @if !config.site_tagline.trim().is_empty() {
-                                    p class="atlas-hero-lede atlas-muted" { (config.site_tagline) }
-                                }
-                                @if !config.hero_subtitle.trim().is_empty() {
-                                    p class="atlas-hero-subtitle atlas-muted" { (config.hero_subtitle) }
-                                }
-                                @if !config.nav_cta_label.trim().is_empty() && !config.nav_cta_url.trim().is_empty() {
-                                    a class="atlas-cta atlas-cta-strong" href=(config.nav_cta_url) {
-                                        (config.nav_cta_label)
-                                        span aria-hidden="true" { "→" }
-                                    }
-                                }
-                            }
-                            div class="atlas-card atlas-hero-card" {
-                                @if let Some(hero_image) = &config.hero_image {
-                                    img class="atlas-hero-image" src=(hero_image) alt="Hero image";
-                                } @else {
-                                    div class="atlas-card-stack" {
-                                        div class="atlas-card-meta" {
-                                            span class="atlas-overline atlas-muted" { (path) }
-                                            span class="atlas-overline atlas-muted" { "Atlas" }
-                                        }
-                                        div class="atlas-card-copy" {
-                                            h3 { "Page overview" }
-                                            p class="atlas-muted" { "Each route renders independently for fast, focused navigation." }
-                                        }
-                                        div class="atlas-card-grid" {
-                                            div class="atlas-card-mini atlas-border" {
-                                                p class="atlas-card-title" { "Readable content" }
-                                                p class="atlas-muted" { "Balanced line lengths and generous spacing." }
-                                            }
-                                            div class="atlas-card-mini atlas-border" {
-                                                p class="atlas-card-title" { "Tailwind-ready" }
-                                                p class="atlas-muted" { "Utility classes guide layout and rhythm." }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+                @if hero.show {
+                    (hero_section(config, hero, page_title, container_class))
                 }
                 section class=(format!("atlas-container atlas-content {}", container_class)) {
                     div class="content-stack" { (content) }
@@ -128,20 +118,132 @@ pub fn page_layout(
     }
 }
 
-fn container_width_class(value: &str) -> &'static str {
-    match value {
-        "narrow" => "atlas-container-narrow",
-        "wide" => "atlas-container-wide",
-        "full" => "atlas-container-full",
-        _ => "atlas-container-wide",
+fn hero_section(
+    config: &AtlasThemeConfig,
+    hero: &HeroSettings,
+    page_title: &str,
+    container_class: &str,
+) -> Markup {
+    match hero.layout {
+        HeroLayout::Split if hero.image.is_some() => html! {
+            section class="atlas-hero atlas-hero-split atlas-border" {
+                div class=(format!("atlas-container atlas-hero-inner {}", container_class)) {
+                    (hero_copy(config, hero, page_title))
+                    @if let Some(hero_image) = &hero.image {
+                        div class="atlas-card atlas-hero-card" {
+                            img class="atlas-hero-image" src=(hero_image) alt="Hero image";
+                        }
+                    }
+                }
+            }
+        },
+        // Without an image there is nothing for the second column.
+        HeroLayout::Split | HeroLayout::Centered => html! {
+            section class="atlas-hero atlas-hero-centered atlas-border" {
+                div class=(format!("atlas-container atlas-hero-inner {}", container_class)) {
+                    (hero_copy(config, hero, page_title))
+                    @if let Some(hero_image) = &hero.image {
+                        img class="atlas-hero-image" src=(hero_image) alt="Hero image";
+                    }
+                }
+            }
+        },
+        HeroLayout::FullBleed => html! {
+            section
+                class="atlas-hero atlas-hero-full-bleed"
+                style=[hero.image.as_deref().and_then(background_image)]
+            {
+                div class=(format!("atlas-container atlas-hero-inner {}", container_class)) {
+                    (hero_copy(config, hero, page_title))
+                }
+            }
+        },
+        HeroLayout::Compact => html! {
+            section class="atlas-hero atlas-hero-compact atlas-border" {
+                div class=(format!("atlas-container atlas-hero-inner {}", container_class)) {
+                    h1 class="atlas-hero-title" { (page_title) }
+                }
+            }
+        },
+        HeroLayout::None => html! {},
     }
 }
 
-fn header_style_class(value: &str) -> &'static str {
-    match value {
-        "solid" => "atlas-header-solid",
-        "glass" => "atlas-glass",
-        _ => "atlas-header-solid",
+fn hero_copy(config: &AtlasThemeConfig, hero: &HeroSettings, page_title: &str) -> Markup {
+    html! {
+        div class="atlas-hero-content" {
+            @if !hero.overline.trim().is_empty() {
+                span class="atlas-pill" { (hero.overline) }
+            }
+            h1 class="atlas-hero-title" { (page_title) }
+            @if !config.site_tagline.trim().is_empty() {
+                p class="atlas-hero-lede atlas-muted" { (config.site_tagline) }
+            }
+            @if !hero.subtitle.trim().is_empty() {
+                p class="atlas-hero-subtitle atlas-muted" { (hero.subtitle) }
+            }
+            @if !config.nav_cta_label.trim().is_empty() && !config.nav_cta_url.trim().is_empty() {
+                a class="atlas-cta atlas-cta-strong" href=(config.nav_cta_url) {
+                    (config.nav_cta_label)
+                    span aria-hidden="true" { "→" }
+                }
+            }
+        }
+    }
+}
+
+fn container_width_class(width: LayoutWidth) -> &'static str {
+    match width {
+        LayoutWidth::Narrow => "atlas-container-narrow",
+        LayoutWidth::Wide => "atlas-container-wide",
+        LayoutWidth::Full => "atlas-container-full",
+    }
+}
+
+fn header_style_class(style: HeaderStyle) -> &'static str {
+    match style {
+        HeaderStyle::Solid => "atlas-header-solid",
+        HeaderStyle::Glass => "atlas-glass",
+    }
+}
+
+/// Dropdowns are rendered closed, with `aria-expanded` to match until the
+/// script takes over.
+fn nav_entry(item: &NavItem) -> Markup {
+    html! {
+        @if item.children.is_empty() {
+            li { (nav_link(item)) }
+        } @else {
+            li class="atlas-nav-group" {
+                details class="atlas-nav-dropdown" {
+                    summary class=(nav_link_class(item.active)) aria-expanded="false" { (item.label) }
+                    ul class="atlas-nav-submenu" {
+                        @if item.href.is_some() {
+                            li { (nav_link(item)) }
+                        }
+                        @for child in &item.children {
+                            (nav_entry(child))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn nav_link(item: &NavItem) -> Markup {
+    html! {
+        @match &item.href {
+            Some(href) if item.external => {
+                a class=(nav_link_class(item.current)) href=(href) target="_blank" rel="noopener noreferrer" { (item.label) }
+            }
+            Some(href) => {
+                a class=(nav_link_class(item.current)) href=(href) aria-current=[item.current.then_some("page")] { (item.label) }
+            }
+            None => {
+                span class=(nav_link_class(item.active)) { (item.label) }
+            }
+        }
     }
 }
 
@@ -152,3 +254,94 @@ fn nav_link_class(active: bool) -> &'static str {
         "atlas-nav-link"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(label: &str, href: &str, current: bool) -> NavItem {
+        NavItem {
+            label: label.to_string(),
+            href: Some(href.to_string()),
+            current,
+            active: current,
+            external: false,
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn items_without_children_render_as_links() {
+        let html = nav_entry(&link("Music", "/music", true)).into_string();
+
+        assert!(!html.contains("<details"));
+        assert!(html.contains(r#"href="/music""#));
+        assert!(html.contains(r#"aria-current="page""#));
+    }
+
+    #[test]
+    fn submenus_render_as_closed_dropdowns() {
+        let about = NavItem {
+            active: true,
+            children: vec![link("Team", "/about/team", true)],
+            ..link("About", "/about", false)
+        };
+
+        let html = nav_entry(&about).into_string();
+
+        assert!(html.contains(r#"<details class="atlas-nav-dropdown">"#));
+        assert!(html.contains(r#"aria-expanded="false""#));
+        assert!(html.contains(r#"<ul class="atlas-nav-submenu">"#));
+        // The parent page stays reachable from inside its own dropdown.
+        assert!(html.contains(r#"href="/about""#));
+        assert!(html.contains(r#"href="/about/team" aria-current="page""#));
+    }
+
+    #[test]
+    fn groups_without_a_page_only_link_their_children() {
+        let more = NavItem {
+            href: None,
+            children: vec![link("Press", "/press", false)],
+            ..link("More", "/more", false)
+        };
+
+        let html = nav_entry(&more).into_string();
+
+        assert!(html.contains(r#"href="/press""#));
+        assert!(!html.contains(r#"href="/more""#));
+    }
+
+    fn split_hero(image: Option<&str>) -> HeroSettings {
+        HeroSettings {
+            show: true,
+            overline: String::new(),
+            subtitle: String::new(),
+            image: image.map(str::to_string),
+            layout: HeroLayout::Split,
+        }
+    }
+
+    #[test]
+    fn split_heroes_show_the_image_beside_the_copy() {
+        let html = hero_section(
+            &AtlasThemeConfig::default(),
+            &split_hero(Some("/_f/hero.jpg")),
+            "About",
+            "",
+        )
+        .into_string();
+
+        assert!(html.contains("atlas-hero-split"));
+        assert!(html.contains(r#"src="/_f/hero.jpg""#));
+    }
+
+    #[test]
+    fn split_heroes_without_an_image_are_centred() {
+        let html = hero_section(&AtlasThemeConfig::default(), &split_hero(None), "About", "")
+            .into_string();
+
+        assert!(html.contains("atlas-hero-centered"));
+        assert!(!html.contains("atlas-hero-split"));
+        assert!(!html.contains("atlas-hero-card"));
+    }
+}
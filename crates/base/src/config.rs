@@ -0,0 +1,186 @@
+use canopie_utils::themes::ThemeSchema;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Key under which a stored theme config records its schema version.
+pub const CONFIG_VERSION_KEY: &str = "_version";
+
+/// Upgrades a stored config by one schema version.
+pub type ConfigMigration = fn(Value) -> Value;
+
+/// Links a theme to the struct that holds its website-level configuration.
+pub trait GetThemeConfig {
+    type Config: ThemeSchema + Default + Serialize;
+
+    /// Forward migrations, oldest first. A config stored at version `n` is
+    /// upgraded by running every migration from index `n` onwards, so entries
+    /// must only ever be appended.
+    fn migrations() -> Vec<ConfigMigration> {
+        vec![]
+    }
+}
+
+/// A single invalid field in a stored theme config.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    /// Path to the offending field, e.g. `accent_color`. Empty for the config itself.
+    pub path: String,
+    pub reason: String,
+}
+
+impl FieldError {
+    pub fn new(path: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Reads the schema version of a stored config. Configs saved before
+/// versioning was introduced have no version and count as version 0.
+pub fn config_version(value: &Value) -> u64 {
+    value
+        .get(CONFIG_VERSION_KEY)
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Runs every migration the stored config has not seen yet and stamps it with
+/// the current version of `T`.
+pub fn upgrade_config<T: GetThemeConfig>(value: Value) -> Value {
+    let migrations = T::migrations();
+    let version = config_version(&value);
+    let current = migrations.len() as u64;
+
+    if version > current {
+        tracing::warn!(
+            "Theme config is at version {} but this release only knows {}, leaving it as is",
+            version,
+            current
+        );
+        return value;
+    }
+
+    let mut value = migrations
+        .into_iter()
+        .skip(version as usize)
+        .fold(value, |value, migration| migration(value));
+
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert(CONFIG_VERSION_KEY.to_string(), Value::from(current));
+    }
+
+    value
+}
+
+/// The default config of theme `T`, stamped with the current schema version.
+pub fn default_config<T: GetThemeConfig>() -> Value {
+    let value = serde_json::to_value(T::Config::default()).unwrap_or_default();
+    upgrade_config::<T>(value)
+}
+
+/// Validates every field of `value` against the config of theme `T` and
+/// reports each failure. The config is upgraded to the current version first.
+///
+/// Each field is checked on its own, with every other field left to its
+/// default, so one bad field never hides another.
+pub fn validate_config<T: GetThemeConfig>(value: &Value) -> Result<(), Vec<FieldError>> {
+    let value = without_version(upgrade_config::<T>(value.clone()));
+    let Some(fields) = value.as_object() else {
+        return Err(vec![FieldError::new(
+            "",
+            "theme config must be a JSON object",
+        )]);
+    };
+
+    let known_fields = known_fields::<T::Config>();
+    let errors: Vec<FieldError> = fields
+        .iter()
+        .filter_map(|(key, field)| {
+            if !known_fields.contains(key) {
+                return Some(FieldError::new(key.as_str(), "unknown field"));
+            }
+            check_field::<T::Config>(key, field).err()
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Builds the config of theme `T` from a stored value, upgrading it first and
+/// then keeping every valid field, falling back to the default for the
+/// invalid ones.
+pub fn resolve_config<T: GetThemeConfig>(value: Option<&Value>) -> T::Config {
+    let Some(value) = value else {
+        return T::Config::default();
+    };
+
+    resolve_fields(without_version(upgrade_config::<T>(value.clone())))
+}
+
+/// Builds `C` from a JSON object, keeping every valid field and falling back
+/// to the default for the invalid ones. Used directly for page configs, which
+/// are not versioned.
+pub fn resolve_fields<C>(value: Value) -> C
+where
+    C: ThemeSchema + Default,
+{
+    if value.is_null() {
+        return C::default();
+    }
+
+    if let Ok(config) = C::from_json_value(value.clone()) {
+        return config;
+    }
+
+    let Some(fields) = value.as_object() else {
+        tracing::warn!("Theme config is not a JSON object, using defaults");
+        return C::default();
+    };
+
+    let mut valid = Map::new();
+    for (key, field) in fields {
+        match check_field::<C>(key, field) {
+            Ok(()) => {
+                valid.insert(key.clone(), field.clone());
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Ignoring theme config field {}: {}",
+                    error.path,
+                    error.reason
+                );
+            }
+        }
+    }
+
+    C::from_json_value(Value::Object(valid)).unwrap_or_default()
+}
+
+fn without_version(mut value: Value) -> Value {
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove(CONFIG_VERSION_KEY);
+    }
+    value
+}
+
+fn check_field<T: ThemeSchema>(key: &str, field: &Value) -> Result<(), FieldError> {
+    let mut single = Map::new();
+    single.insert(key.to_string(), field.clone());
+
+    T::from_json_value(Value::Object(single))
+        .map(|_| ())
+        .map_err(|error| FieldError::new(key, error.to_string()))
+}
+
+fn known_fields<T: Default + Serialize>() -> Vec<String> {
+    match serde_json::to_value(T::default()) {
+        Ok(Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,120 @@
+use canopie_utils::{
+    models::PageContent,
+    types::tiptap::{TipTapNode, empty_tiptap_node, to_tiptap_node},
+};
+use serde_json::{Value, json};
+
+use crate::diagnostics::Diagnostics;
+
+/// Text shown in place of a node that failed to parse, in preview only.
+const PLACEHOLDER_TEXT: &str = "This block could not be displayed.";
+
+/// Whether a page is rendered for visitors or as a preview for its editors.
+/// Chosen by whoever asks for the render, not by the content loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    #[default]
+    Live,
+    Preview,
+}
+
+/// Parses a page's TipTap document, skipping only the nodes that fail.
+///
+/// Each failing node is reported to `diagnostics` with its JSON path. When
+/// `diagnostics` is collecting for a [`RenderMode::Preview`] render it is
+/// replaced by a placeholder; otherwise it is left out.
+pub fn parse_page_content(
+    page_id: &str,
+    page_content: PageContent,
+    diagnostics: &Diagnostics,
+) -> TipTapNode {
+    parse_content(
+        page_id,
+        page_content.content,
+        diagnostics.mode() == RenderMode::Preview,
+        diagnostics,
+    )
+}
+
+/// [`parse_page_content`] for a document that is not stored as page content.
+pub fn parse_content(
+    page_id: &str,
+    content: Value,
+    preview: bool,
+    diagnostics: &Diagnostics,
+) -> TipTapNode {
+    if let Ok(node) = to_tiptap_node(content.clone()) {
+        return node;
+    }
+
+    let recovery = Recovery {
+        page_id,
+        preview,
+        diagnostics,
+    };
+
+    recovery
+        .node(content, "$")
+        .and_then(|content| to_tiptap_node(content).ok())
+        .unwrap_or_else(|| empty_tiptap_node(None))
+}
+
+struct Recovery<'a> {
+    page_id: &'a str,
+    preview: bool,
+    diagnostics: &'a Diagnostics,
+}
+
+impl Recovery<'_> {
+    /// The node at `path` with its failing descendants dropped, or a
+    /// placeholder (or nothing) when the node itself cannot be parsed.
+    fn node(&self, value: Value, path: &str) -> Option<Value> {
+        let error = match to_tiptap_node(value.clone()) {
+            Ok(_node) => return Some(value),
+            Err(error) => error,
+        };
+
+        if let Some(repaired) = self.children(value, path) {
+            return Some(repaired);
+        }
+
+        self.diagnostics.error(
+            Some(self.page_id),
+            Some(path),
+            format!("Skipped content node: {}", error),
+        );
+
+        self.preview.then(placeholder)
+    }
+
+    /// Retries a node with its failing children dropped, as long as the node
+    /// parses on its own.
+    fn children(&self, value: Value, path: &str) -> Option<Value> {
+        let Value::Object(mut node) = value else {
+            return None;
+        };
+        let Some(Value::Array(children)) = node.remove("content") else {
+            return None;
+        };
+
+        node.insert("content".to_string(), Value::Array(vec![]));
+        to_tiptap_node(Value::Object(node.clone())).ok()?;
+
+        let children = children
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, child)| self.node(child, &format!("{}.content[{}]", path, index)))
+            .collect();
+        node.insert("content".to_string(), Value::Array(children));
+
+        let repaired = Value::Object(node);
+        to_tiptap_node(repaired.clone()).ok().map(|_node| repaired)
+    }
+}
+
+fn placeholder() -> Value {
+    json!({
+        "type": "paragraph",
+        "content": [{ "type": "text", "text": PLACEHOLDER_TEXT }],
+    })
+}
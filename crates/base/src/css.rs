@@ -0,0 +1,65 @@
+/// A CSS `url()` for a URL that came from site content.
+///
+/// Quotes, parentheses, backslashes, whitespace and control characters are
+/// percent-encoded, so the URL cannot close the `url()` or the declaration
+/// it sits in. Empty URLs give `None`.
+pub fn css_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return None;
+    }
+
+    let mut encoded = String::with_capacity(url.len());
+    for character in url.chars() {
+        match character {
+            '\'' | '"' | '(' | ')' | '\\' => encoded.push_str(&percent_encode(character)),
+            character if character.is_ascii_whitespace() || character.is_ascii_control() => {
+                encoded.push_str(&percent_encode(character))
+            }
+            character => encoded.push(character),
+        }
+    }
+
+    Some(format!("url('{}')", encoded))
+}
+
+/// A `background-image` declaration for an uploaded image, or `None` when
+/// there is no image.
+pub fn background_image(url: &str) -> Option<String> {
+    css_url(url).map(|url| format!("background-image: {};", url))
+}
+
+fn percent_encode(character: char) -> String {
+    format!("%{:02X}", character as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_urls_are_quoted() {
+        assert_eq!(
+            background_image("/_f/band.jpg"),
+            Some("background-image: url('/_f/band.jpg');".to_string())
+        );
+    }
+
+    #[test]
+    fn characters_that_end_the_url_are_encoded() {
+        assert_eq!(
+            css_url("/_f/a'b\"c(d)e\\f g.jpg"),
+            Some("url('/_f/a%27b%22c%28d%29e%5Cf%20g.jpg')".to_string())
+        );
+        assert_eq!(
+            css_url("x.jpg'); background: red; ('"),
+            Some("url('x.jpg%27%29;%20background:%20red;%20%28%27')".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_urls_have_no_declaration() {
+        assert_eq!(background_image(""), None);
+        assert_eq!(background_image("  "), None);
+    }
+}
@@ -0,0 +1,397 @@
+use std::path::Path;
+
+use canopie_utils::{
+    db::PgPool,
+    header::Header,
+    models::{Menu, MenuItem, MenuItemLabel, Page, PageContent, Project, Website},
+    schema::{menu_item_labels, menu_items, menus, page_content, pages, projects, websites},
+    theme_utils::{get_menus, get_page},
+};
+use chrono::NaiveDateTime;
+use diesel::{
+    dsl::{count_star, max},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{content::RenderMode, page::RenderOutcome};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod test_support;
+
+/// A page together with the content that should be rendered for it, if any.
+pub type PageWithContent = (Page, Option<PageContent>);
+
+/// The site content a theme reads while rendering.
+///
+/// Implemented for [`PgPool`] for live sites and for [`MemorySiteData`] for
+/// tests, previews and offline tools.
+pub trait SiteData {
+    fn website(&self, website_id: &str) -> Option<Website>;
+    fn menus(&self, website_id: &str) -> Vec<(Menu, Vec<MenuItem>)>;
+    fn page(&self, website_id: &str, path: &str) -> Option<PageWithContent>;
+    fn page_from_id(&self, page_id: &str) -> Option<PageWithContent>;
+    /// The pages with the given ids and their content, fetched together.
+    /// Unknown ids are left out.
+    fn pages_by_ids(&self, page_ids: &[String]) -> Vec<PageWithContent>;
+    fn pages(&self, website_id: &str) -> Vec<Page>;
+    /// Every label written for the items of the website's menus.
+    fn menu_item_labels(&self, website_id: &str) -> Vec<MenuItemLabel>;
+    /// The default language of the project that owns the website.
+    fn default_language(&self, website_id: &str) -> Option<String>;
+    /// The version of the website's pages, page content, menus, menu items
+    /// and menu item labels, or `None` when it could not be read.
+    fn content_version(&self, website_id: &str) -> Option<ContentVersion>;
+}
+
+/// Changes whenever a website's content is edited: the newest `updated_at`
+/// across its content tables, and how many rows they hold between them.
+/// Deleting a row does not move the newest timestamp, but it does change the
+/// row count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentVersion {
+    pub last_updated: Option<NaiveDateTime>,
+    pub rows: i64,
+}
+
+/// Picks the content to render from a page's content rows: published rows
+/// only, the default content first, then the most recently updated.
+///
+/// Every [`SiteData`] lookup goes through this, so a page renders the same
+/// content whether it is loaded by path, by id or with a batch of menu pages.
+pub fn select_content(contents: impl IntoIterator<Item = PageContent>) -> Option<PageContent> {
+    contents
+        .into_iter()
+        .filter(|content| !content.preview)
+        .max_by_key(|content| (content.default_content, content.updated_at))
+}
+
+/// Signature of [`RenderSite::render_site`], for storing renderers by value.
+pub type SiteRenderer = fn(&dyn SiteData, &mut Header, Website, &str, RenderMode) -> RenderOutcome;
+
+/// Renders a theme from any [`SiteData`], not just a live database.
+pub trait RenderSite {
+    fn render_site(
+        data: &dyn SiteData,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+        mode: RenderMode,
+    ) -> RenderOutcome;
+}
+
+impl SiteData for PgPool {
+    fn website(&self, website_id: &str) -> Option<Website> {
+        let mut connection = self.get().ok()?;
+
+        websites::table
+            .find(website_id)
+            .select(Website::as_select())
+            .first(&mut connection)
+            .ok()
+    }
+
+    fn menus(&self, website_id: &str) -> Vec<(Menu, Vec<MenuItem>)> {
+        get_menus(self, &website_id.to_string())
+    }
+
+    fn page(&self, website_id: &str, path: &str) -> Option<PageWithContent> {
+        let page = get_page(self, &website_id.to_string(), path)?.page;
+
+        // `get_page` resolves the path; the content is picked by the same
+        // rule as every other lookup.
+        self.pages_by_ids(std::slice::from_ref(&page.id)).pop()
+    }
+
+    fn page_from_id(&self, page_id: &str) -> Option<PageWithContent> {
+        self.pages_by_ids(&[page_id.to_string()]).pop()
+    }
+
+    fn pages_by_ids(&self, page_ids: &[String]) -> Vec<PageWithContent> {
+        let Ok(mut connection) = self.get() else {
+            return vec![];
+        };
+
+        let pages: Vec<Page> = pages::table
+            .filter(pages::id.eq_any(page_ids))
+            .select(Page::as_select())
+            .load(&mut connection)
+            .unwrap_or_default();
+        let contents: Vec<PageContent> = page_content::table
+            .filter(page_content::page_id.eq_any(page_ids))
+            .filter(page_content::preview.eq(false))
+            .select(PageContent::as_select())
+            .load(&mut connection)
+            .unwrap_or_default();
+
+        pages
+            .into_iter()
+            .map(|page| {
+                let content = select_content(
+                    contents
+                        .iter()
+                        .filter(|content| content.page_id == page.id)
+                        .cloned(),
+                );
+                (page, content)
+            })
+            .collect()
+    }
+
+    fn pages(&self, website_id: &str) -> Vec<Page> {
+        let Ok(mut connection) = self.get() else {
+            return vec![];
+        };
+
+        pages::table
+            .filter(pages::website_id.eq(website_id))
+            .select(Page::as_select())
+            .load(&mut connection)
+            .unwrap_or_default()
+    }
+
+    fn menu_item_labels(&self, website_id: &str) -> Vec<MenuItemLabel> {
+        let Ok(mut connection) = self.get() else {
+            return vec![];
+        };
+
+        let website_menus = menus::table
+            .filter(menus::website_id.eq(website_id))
+            .select(menus::id);
+
+        menu_item_labels::table
+            .inner_join(menu_items::table)
+            .filter(menu_items::menu_id.eq_any(website_menus))
+            .select(MenuItemLabel::as_select())
+            .load(&mut connection)
+            .unwrap_or_default()
+    }
+
+    fn default_language(&self, website_id: &str) -> Option<String> {
+        let mut connection = self.get().ok()?;
+
+        websites::table
+            .inner_join(projects::table)
+            .filter(websites::id.eq(website_id))
+            .select(projects::default_language)
+            .first(&mut connection)
+            .ok()
+    }
+
+    fn content_version(&self, website_id: &str) -> Option<ContentVersion> {
+        let mut connection = self.get().ok()?;
+
+        let website_menus = menus::table
+            .filter(menus::website_id.eq(website_id))
+            .select(menus::id);
+
+        // Any failing query gives `None`, never a partial version.
+        let tables: [(Option<NaiveDateTime>, i64); 5] = [
+            pages::table
+                .filter(pages::website_id.eq(website_id))
+                .select((max(pages::updated_at), count_star()))
+                .first(&mut connection)
+                .ok()?,
+            page_content::table
+                .inner_join(pages::table)
+                .filter(pages::website_id.eq(website_id))
+                .select((max(page_content::updated_at), count_star()))
+                .first(&mut connection)
+                .ok()?,
+            menus::table
+                .filter(menus::website_id.eq(website_id))
+                .select((max(menus::updated_at), count_star()))
+                .first(&mut connection)
+                .ok()?,
+            menu_items::table
+                .filter(menu_items::menu_id.eq_any(website_menus.clone()))
+                .select((max(menu_items::updated_at), count_star()))
+                .first(&mut connection)
+                .ok()?,
+            menu_item_labels::table
+                .inner_join(menu_items::table)
+                .filter(menu_items::menu_id.eq_any(website_menus))
+                .select((max(menu_item_labels::updated_at), count_star()))
+                .first(&mut connection)
+                .ok()?,
+        ];
+
+        Some(ContentVersion {
+            last_updated: tables
+                .iter()
+                .filter_map(|(updated_at, _)| *updated_at)
+                .max(),
+            rows: tables.iter().map(|(_, rows)| rows).sum(),
+        })
+    }
+}
+
+/// Site content held in memory, usually loaded from a JSON fixture with the
+/// same shape as the database tables.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MemorySiteData {
+    pub projects: Vec<Project>,
+    pub websites: Vec<Website>,
+    pub menus: Vec<Menu>,
+    pub menu_items: Vec<MenuItem>,
+    pub menu_item_labels: Vec<MenuItemLabel>,
+    pub pages: Vec<Page>,
+    pub page_contents: Vec<PageContent>,
+}
+
+impl MemorySiteData {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+
+        Self::from_json(&json)
+            .map_err(|error| format!("Invalid fixture {}: {}", path.display(), error))
+    }
+
+    fn content_for(&self, page: &Page) -> Option<PageContent> {
+        select_content(
+            self.page_contents
+                .iter()
+                .filter(|content| content.page_id == page.id)
+                .cloned(),
+        )
+    }
+}
+
+impl SiteData for MemorySiteData {
+    fn website(&self, website_id: &str) -> Option<Website> {
+        self.websites
+            .iter()
+            .find(|website| website.id == website_id)
+            .cloned()
+    }
+
+    fn menus(&self, website_id: &str) -> Vec<(Menu, Vec<MenuItem>)> {
+        self.menus
+            .iter()
+            .filter(|menu| menu.website_id == website_id)
+            .map(|menu| {
+                let items = self
+                    .menu_items
+                    .iter()
+                    .filter(|item| item.menu_id == menu.id)
+                    .cloned()
+                    .collect();
+                (menu.clone(), items)
+            })
+            .collect()
+    }
+
+    fn page(&self, website_id: &str, path: &str) -> Option<PageWithContent> {
+        let slug = path.trim_matches('/');
+
+        let page = self.pages.iter().find(|page| {
+            page.website_id == website_id
+                && if slug.is_empty() {
+                    page.home || page.slug.is_empty()
+                } else {
+                    page.slug == slug
+                }
+        })?;
+
+        Some((page.clone(), self.content_for(page)))
+    }
+
+    fn page_from_id(&self, page_id: &str) -> Option<PageWithContent> {
+        let page = self.pages.iter().find(|page| page.id == page_id)?;
+
+        Some((page.clone(), self.content_for(page)))
+    }
+
+    fn pages_by_ids(&self, page_ids: &[String]) -> Vec<PageWithContent> {
+        page_ids
+            .iter()
+            .filter_map(|page_id| self.page_from_id(page_id))
+            .collect()
+    }
+
+    fn pages(&self, website_id: &str) -> Vec<Page> {
+        self.pages
+            .iter()
+            .filter(|page| page.website_id == website_id)
+            .cloned()
+            .collect()
+    }
+
+    fn menu_item_labels(&self, website_id: &str) -> Vec<MenuItemLabel> {
+        let menu_ids: Vec<&str> = self
+            .menus
+            .iter()
+            .filter(|menu| menu.website_id == website_id)
+            .map(|menu| menu.id.as_str())
+            .collect();
+
+        self.menu_item_labels
+            .iter()
+            .filter(|label| {
+                self.menu_items.iter().any(|item| {
+                    item.id == label.menu_item_id && menu_ids.contains(&item.menu_id.as_str())
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn default_language(&self, website_id: &str) -> Option<String> {
+        let website = self.website(website_id)?;
+
+        self.projects
+            .iter()
+            .find(|project| project.id == website.project_id)
+            .map(|project| project.default_language.clone())
+    }
+
+    fn content_version(&self, website_id: &str) -> Option<ContentVersion> {
+        let pages: Vec<&Page> = self
+            .pages
+            .iter()
+            .filter(|page| page.website_id == website_id)
+            .collect();
+        let menus: Vec<&Menu> = self
+            .menus
+            .iter()
+            .filter(|menu| menu.website_id == website_id)
+            .collect();
+        let menu_items: Vec<&MenuItem> = self
+            .menu_items
+            .iter()
+            .filter(|item| menus.iter().any(|menu| menu.id == item.menu_id))
+            .collect();
+        let contents: Vec<&PageContent> = self
+            .page_contents
+            .iter()
+            .filter(|content| pages.iter().any(|page| page.id == content.page_id))
+            .collect();
+        let labels: Vec<&MenuItemLabel> = self
+            .menu_item_labels
+            .iter()
+            .filter(|label| menu_items.iter().any(|item| item.id == label.menu_item_id))
+            .collect();
+
+        let last_updated = pages
+            .iter()
+            .map(|page| page.updated_at)
+            .chain(menus.iter().map(|menu| menu.updated_at))
+            .chain(menu_items.iter().map(|item| item.updated_at))
+            .chain(contents.iter().map(|content| content.updated_at))
+            .chain(labels.iter().map(|label| label.updated_at))
+            .max();
+        let rows = pages.len() + menus.len() + menu_items.len() + contents.len() + labels.len();
+
+        Some(ContentVersion {
+            last_updated,
+            rows: rows as i64,
+        })
+    }
+}
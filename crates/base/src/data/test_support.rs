@@ -0,0 +1,178 @@
+//! Fixture builders and [`SiteData`] wrappers shared by the theme crates'
+//! tests. Enabled with the `testing` feature.
+//!
+//! The builders return rows as JSON, in the shape of the database tables, so
+//! they can go straight into a [`MemorySiteData`] or be parsed with [`row`].
+
+use std::cell::{Cell, RefCell};
+
+use canopie_utils::models::{Menu, MenuItem, MenuItemLabel, Page, Website};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use super::{ContentVersion, MemorySiteData, PageWithContent, SiteData};
+
+/// The `created_at` and `updated_at` of every built row.
+pub const TIMESTAMP: &str = "2025-01-01T00:00:00";
+
+/// The website in the JSON fixtures under `tests/fixtures`.
+pub const WEBSITE_ID: &str = "site-fixture";
+
+/// Loads `tests/fixtures/<name>` from the workspace root.
+pub fn fixture(name: &str) -> MemorySiteData {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/fixtures/");
+    MemorySiteData::from_file(format!("{}{}", path, name))
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Parses a built row into its model.
+pub fn row<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn menu(id: &str, name: &str, active: i32) -> Value {
+    json!({
+        "id": id, "created_at": TIMESTAMP, "updated_at": TIMESTAMP, "name": name,
+        "description": null, "active": active, "website_id": "site"
+    })
+}
+
+pub fn menu_item(
+    id: &str,
+    menu_id: &str,
+    parent_id: Option<&str>,
+    page_id: Option<&str>,
+    active: i32,
+) -> Value {
+    json!({
+        "id": id, "created_at": TIMESTAMP, "updated_at": TIMESTAMP, "menu_id": menu_id,
+        "parent_id": parent_id, "type_": "page", "order": 0, "page_id": page_id,
+        "submenu_id": null, "url": null, "active": active
+    })
+}
+
+/// A page titled with its id.
+pub fn page(id: &str, slug: &str, active: i32) -> Value {
+    json!({
+        "id": id, "created_at": TIMESTAMP, "updated_at": TIMESTAMP, "title": id,
+        "slug": slug, "type_": "page", "options": {}, "active": active, "home": false,
+        "website_id": "site", "template": null
+    })
+}
+
+pub fn project(id: &str) -> Value {
+    json!({
+        "id": id, "created_at": TIMESTAMP, "updated_at": TIMESTAMP, "name": null,
+        "type_": "band", "active": 1, "default_language": "en"
+    })
+}
+
+pub fn project_extra(project_id: &str, extra: &str, active: i32) -> Value {
+    json!({
+        "id": format!("{}-{}", project_id, extra), "created_at": TIMESTAMP,
+        "updated_at": TIMESTAMP, "project_id": project_id, "extra": extra, "active": active
+    })
+}
+
+/// Forwards to a [`MemorySiteData`], counting the page lookups themes make
+/// and recording the paths they look pages up by.
+pub struct CountingSiteData {
+    pub inner: MemorySiteData,
+    pub page_from_id: Cell<usize>,
+    pub pages_by_ids: Cell<usize>,
+    pub paths: RefCell<Vec<String>>,
+}
+
+impl CountingSiteData {
+    pub fn new(inner: MemorySiteData) -> Self {
+        Self {
+            inner,
+            page_from_id: Cell::new(0),
+            pages_by_ids: Cell::new(0),
+            paths: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl SiteData for CountingSiteData {
+    fn website(&self, website_id: &str) -> Option<Website> {
+        self.inner.website(website_id)
+    }
+
+    fn menus(&self, website_id: &str) -> Vec<(Menu, Vec<MenuItem>)> {
+        self.inner.menus(website_id)
+    }
+
+    fn page(&self, website_id: &str, path: &str) -> Option<PageWithContent> {
+        self.paths.borrow_mut().push(path.to_string());
+        self.inner.page(website_id, path)
+    }
+
+    fn page_from_id(&self, page_id: &str) -> Option<PageWithContent> {
+        self.page_from_id.set(self.page_from_id.get() + 1);
+        self.inner.page_from_id(page_id)
+    }
+
+    fn pages_by_ids(&self, page_ids: &[String]) -> Vec<PageWithContent> {
+        self.pages_by_ids.set(self.pages_by_ids.get() + 1);
+        self.inner.pages_by_ids(page_ids)
+    }
+
+    fn pages(&self, website_id: &str) -> Vec<Page> {
+        self.inner.pages(website_id)
+    }
+
+    fn menu_item_labels(&self, website_id: &str) -> Vec<MenuItemLabel> {
+        self.inner.menu_item_labels(website_id)
+    }
+
+    fn default_language(&self, website_id: &str) -> Option<String> {
+        self.inner.default_language(website_id)
+    }
+
+    fn content_version(&self, website_id: &str) -> Option<ContentVersion> {
+        self.inner.content_version(website_id)
+    }
+}
+
+/// A site whose content version cannot be read, as when the database query
+/// for it fails.
+pub struct UnversionedSiteData(pub MemorySiteData);
+
+impl SiteData for UnversionedSiteData {
+    fn website(&self, website_id: &str) -> Option<Website> {
+        self.0.website(website_id)
+    }
+
+    fn menus(&self, website_id: &str) -> Vec<(Menu, Vec<MenuItem>)> {
+        self.0.menus(website_id)
+    }
+
+    fn page(&self, website_id: &str, path: &str) -> Option<PageWithContent> {
+        self.0.page(website_id, path)
+    }
+
+    fn page_from_id(&self, page_id: &str) -> Option<PageWithContent> {
+        self.0.page_from_id(page_id)
+    }
+
+    fn pages_by_ids(&self, page_ids: &[String]) -> Vec<PageWithContent> {
+        self.0.pages_by_ids(page_ids)
+    }
+
+    fn pages(&self, website_id: &str) -> Vec<Page> {
+        self.0.pages(website_id)
+    }
+
+    fn menu_item_labels(&self, website_id: &str) -> Vec<MenuItemLabel> {
+        self.0.menu_item_labels(website_id)
+    }
+
+    fn default_language(&self, website_id: &str) -> Option<String> {
+        self.0.default_language(website_id)
+    }
+
+    fn content_version(&self, _website_id: &str) -> Option<ContentVersion> {
+        None
+    }
+}
@@ -0,0 +1,250 @@
+use std::cell::RefCell;
+
+use canopie_utils::renderer::PageResult;
+#[cfg(feature = "dev")]
+use maud::html;
+use maud::{Markup, PreEscaped};
+
+use crate::content::RenderMode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A content problem found while rendering a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub page_id: Option<String>,
+    /// JSON path of the offending TipTap node, e.g. `$.content[2]`.
+    pub node_path: Option<String>,
+    pub message: String,
+}
+
+/// Collects the [`Diagnostic`]s of one render.
+///
+/// A theme creates one per request, for the [`RenderMode`] its caller asked
+/// for, and passes it down to everything that reads content. Each diagnostic
+/// is emitted through `tracing` as it is recorded; under the `dev` feature
+/// [`Diagnostics::finish`] also adds them to the page as an overlay.
+/// Components rendered through the formatter cannot reach the collector and
+/// report through `tracing` directly.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    mode: RenderMode,
+    entries: RefCell<Vec<Diagnostic>>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_mode(mode: RenderMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    pub fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    pub fn warn(&self, page_id: Option<&str>, node_path: Option<&str>, message: impl Into<String>) {
+        self.record(Severity::Warning, page_id, node_path, message.into());
+    }
+
+    pub fn error(
+        &self,
+        page_id: Option<&str>,
+        node_path: Option<&str>,
+        message: impl Into<String>,
+    ) {
+        self.record(Severity::Error, page_id, node_path, message.into());
+    }
+
+    pub fn entries(&self) -> Vec<Diagnostic> {
+        self.entries.borrow().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Hands back the rendered page, with the diagnostics overlay added under
+    /// the `dev` feature.
+    pub fn finish(&self, result: PageResult) -> PageResult {
+        if self.is_empty() || !cfg!(feature = "dev") {
+            return result;
+        }
+
+        match result {
+            PageResult::Found(markup) => PageResult::Found(self.with_overlay(markup)),
+            PageResult::NotFound(markup) => PageResult::NotFound(self.with_overlay(markup)),
+        }
+    }
+
+    fn record(
+        &self,
+        severity: Severity,
+        page_id: Option<&str>,
+        node_path: Option<&str>,
+        message: String,
+    ) {
+        match severity {
+            Severity::Warning => tracing::warn!(page_id, node_path, "{}", message),
+            Severity::Error => tracing::error!(page_id, node_path, "{}", message),
+        }
+
+        self.entries.borrow_mut().push(Diagnostic {
+            severity,
+            page_id: page_id.map(str::to_string),
+            node_path: node_path.map(str::to_string),
+            message,
+        });
+    }
+
+    /// Puts the overlay just inside the closing `</body>`, or after the page
+    /// when there is none.
+    fn with_overlay(&self, markup: Markup) -> Markup {
+        let overlay = self.overlay().into_string();
+        let mut page = markup.into_string();
+
+        match page.rfind("</body>") {
+            Some(index) => page.insert_str(index, &overlay),
+            None => page.push_str(&overlay),
+        }
+
+        PreEscaped(page)
+    }
+
+    #[cfg(feature = "dev")]
+    fn overlay(&self) -> Markup {
+        let entries = self.entries.borrow();
+
+        html! {
+            details
+                class="canopie-diagnostics"
+                open
+                style="position: fixed; right: 1rem; bottom: 1rem; z-index: 2147483647; max-width: 32rem; max-height: 50vh; overflow: auto; padding: 0.75rem 1rem; border-radius: 0.5rem; background: #111827; color: #f9fafb; font: 12px/1.5 ui-monospace, monospace; box-shadow: 0 10px 30px rgba(0, 0, 0, 0.4);"
+            {
+                summary style="cursor: pointer; font-weight: 700;" {
+                    (entries.len()) " render diagnostic" @if entries.len() != 1 { "s" }
+                }
+                ul style="margin: 0.5rem 0 0; padding: 0; list-style: none;" {
+                    @for entry in entries.iter() {
+                        li style="padding: 0.25rem 0; border-top: 1px solid #374151;" {
+                            @match entry.severity {
+                                Severity::Warning => { span style="color: #fbbf24;" { "warning " } }
+                                Severity::Error => { span style="color: #f87171;" { "error " } }
+                            }
+                            (entry.message)
+                            @if let Some(page_id) = &entry.page_id {
+                                br;
+                                span style="color: #9ca3af;" { "page " (page_id) }
+                            }
+                            @if let Some(node_path) = &entry.node_path {
+                                br;
+                                span style="color: #9ca3af;" { "node " (node_path) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "dev"))]
+    fn overlay(&self) -> Markup {
+        PreEscaped(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maud::html;
+
+    use super::*;
+
+    fn found_body(result: PageResult) -> String {
+        let PageResult::Found(markup) = result else {
+            panic!("finish changed the page status");
+        };
+        markup.into_string()
+    }
+
+    #[test]
+    fn diagnostics_are_recorded_in_order() {
+        let diagnostics = Diagnostics::new();
+
+        diagnostics.warn(Some("page-1"), None, "No content found for page /about");
+        diagnostics.error(Some("page-2"), Some("$.content[1]"), "Unknown node type");
+
+        assert_eq!(
+            diagnostics.entries(),
+            vec![
+                Diagnostic {
+                    severity: Severity::Warning,
+                    page_id: Some("page-1".to_string()),
+                    node_path: None,
+                    message: "No content found for page /about".to_string(),
+                },
+                Diagnostic {
+                    severity: Severity::Error,
+                    page_id: Some("page-2".to_string()),
+                    node_path: Some("$.content[1]".to_string()),
+                    message: "Unknown node type".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "dev"))]
+    #[test]
+    fn finish_leaves_the_page_alone_outside_dev() {
+        let diagnostics = Diagnostics::new();
+        diagnostics.error(Some("page-1"), None, "Error parsing content");
+
+        let page = html! { body { p { "Hello" } } };
+        let expected = page.clone().into_string();
+
+        assert_eq!(
+            found_body(diagnostics.finish(PageResult::Found(page))),
+            expected
+        );
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn finish_adds_the_overlay_before_the_closing_body_tag() {
+        let diagnostics = Diagnostics::new();
+        diagnostics.error(Some("page-1"), Some("$.content[1]"), "Unknown node type");
+
+        let page = html! { body { p { "Hello" } } };
+        let html = found_body(diagnostics.finish(PageResult::Found(page)));
+
+        let overlay = html
+            .find(r#"<details class="canopie-diagnostics""#)
+            .unwrap();
+        assert!(html.starts_with("<body><p>Hello</p>"));
+        assert!(html.ends_with("</details></body>"));
+        assert!(html.find("</p>").unwrap() < overlay);
+        assert!(html[overlay..].contains("Unknown node type"));
+        assert!(html[overlay..].contains("node $.content[1]"));
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn finish_leaves_pages_without_diagnostics_alone() {
+        let page = html! { body { p { "Hello" } } };
+        let expected = page.clone().into_string();
+
+        assert_eq!(
+            found_body(Diagnostics::new().finish(PageResult::Found(page))),
+            expected
+        );
+    }
+}
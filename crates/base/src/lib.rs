@@ -0,0 +1,248 @@
+use canopie_macros::{ThemeConfig, theme_defaults};
+use canopie_utils::themes;
+use canopie_utils::{
+    components::{Asset, AssetOptions, Formatter},
+    header::Header,
+    models::{MenuItem, Website},
+    renderer::PageResponse,
+    themes::{GetThemeOverview, PageThemeOverview, ThemeOverview, ThemeRestriction, ThemeSchema},
+};
+
+#[cfg(feature = "embed")]
+use canopie_utils::{
+    renderer::{GetThemeRenderer, ThemeRenderer},
+    resource::embed::ThemeResource,
+};
+#[cfg(feature = "embed")]
+use include_dir::Dir;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{GetThemeConfig, resolve_config},
+    content::RenderMode,
+    data::{RenderSite, SiteData},
+    diagnostics::Diagnostics,
+    menu::{MenuLabels, MenuPages, pretty_url},
+    page::{PageContext, RenderOutcome, finish_response, render_page_content},
+    registry::ThemeRegistry,
+    templates::{layout::base_layout, not_found::base_not_found},
+};
+
+pub mod config;
+pub mod content;
+pub mod css;
+pub mod data;
+pub mod diagnostics;
+pub mod menu;
+pub mod page;
+pub mod registry;
+pub mod schema;
+mod templates;
+
+#[derive(Debug, Clone)]
+pub struct NavItem {
+    pub label: String,
+    pub href: String,
+    pub active: bool,
+    pub external: bool,
+}
+
+#[theme_defaults]
+#[derive(Serialize, Deserialize, Debug, ThemeConfig)]
+pub struct BaseThemeConfig {
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#1f2937",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
+    pub text_color: String,
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#ffffff",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
+    pub surface_color: String,
+}
+
+impl Default for BaseThemeConfig {
+    fn default() -> Self {
+        Self {
+            site_icon: None,
+            primary_color: "#2563eb".to_string(),
+            text_color: "#1f2937".to_string(),
+            surface_color: "#ffffff".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ThemeConfig)]
+pub struct BasePageConfig {}
+
+/// The neutral theme used when a website's theme id is not recognised.
+pub struct ThemeBase {}
+
+impl GetThemeOverview for ThemeBase {
+    fn get_theme_overview() -> ThemeOverview {
+        ThemeOverview {
+            id: "base".to_string(),
+            name: "Base".to_string(),
+            description: Some("A minimal, unbranded single-column theme.".to_string()),
+            restricted: ThemeRestriction::None,
+            config: BaseThemeConfig::schema(),
+            page_themes: vec![PageThemeOverview {
+                name: "Base Page".to_string(),
+                config: BasePageConfig::schema(),
+                default: true,
+            }],
+        }
+    }
+}
+
+impl GetThemeConfig for ThemeBase {
+    type Config = BaseThemeConfig;
+}
+
+impl RenderSite for ThemeBase {
+    fn render_site(
+        data: &dyn SiteData,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+        mode: RenderMode,
+    ) -> RenderOutcome {
+        base(data, headers, website, path, mode)
+    }
+}
+
+#[cfg(feature = "embed")]
+impl ThemeResource for ThemeBase {
+    fn get_theme_resources() -> Dir<'static> {
+        use include_dir::include_dir;
+
+        let theme_dir: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/static");
+
+        theme_dir
+    }
+    fn attach_theme_resources(
+        resources: &mut canopie_utils::resource::embed::Resources,
+    ) -> Result<(), String> {
+        let id = Self::get_theme_overview().id;
+        resources.add_dir(id.as_str(), Self::get_theme_resources());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embed")]
+impl GetThemeRenderer for ThemeBase {
+    fn get_theme_renderer() -> ThemeRenderer {
+        ThemeRenderer {
+            name: Self::get_theme_overview().id,
+            build_content: |pool, headers, website, path| {
+                base(pool, headers, website, path, RenderMode::Live).into()
+            },
+        }
+    }
+}
+
+pub fn get_theme_overview() -> ThemeOverview {
+    ThemeBase::get_theme_overview()
+}
+
+/// Registers the base theme, as the fallback for unknown theme ids.
+#[cfg(not(feature = "embed"))]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry
+        .register::<ThemeBase>()
+        .with_fallback::<ThemeBase>()
+}
+
+/// Registers the base theme, as the fallback for unknown theme ids.
+#[cfg(feature = "embed")]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry
+        .register::<ThemeBase>()
+        .with_renderer::<ThemeBase>()
+        .with_resources::<ThemeBase>()
+        .with_fallback::<ThemeBase>()
+}
+
+pub fn base<D: SiteData + ?Sized>(
+    data: &D,
+    headers: &mut Header,
+    website: Website,
+    path: &str,
+    mode: RenderMode,
+) -> RenderOutcome {
+    let theme_config = resolve_theme_config(&website);
+    let diagnostics = Diagnostics::for_mode(mode);
+
+    let formatter = Formatter::default(Some(website.clone()));
+    formatter.add_asset(Asset::Style(AssetOptions::new("base")));
+
+    let context = PageContext::load(data, &website.id, path);
+    let nav_items = context
+        .main_menu()
+        .map(|menu| build_nav_items(&menu.1, &context.menu_pages, &context.labels, path))
+        .unwrap_or_default();
+
+    let Some((page, page_content)) = context.page else {
+        let response = base_not_found(&theme_config, &website, &nav_items, formatter);
+        return finish_response(response, "base", headers, &diagnostics);
+    };
+
+    let components = render_page_content(page.id.as_str(), page_content, &formatter, &diagnostics);
+
+    let body = base_layout(
+        &theme_config,
+        &website,
+        page.title.as_str(),
+        &nav_items,
+        components,
+    );
+
+    let response = PageResponse::new(page.title, body, formatter);
+    finish_response(response, "base", headers, &diagnostics)
+}
+
+fn resolve_theme_config(website: &Website) -> BaseThemeConfig {
+    resolve_config::<ThemeBase>(website.theme_config.as_ref())
+}
+
+fn build_nav_items(
+    menu_items: &[MenuItem],
+    pages: &MenuPages,
+    labels: &MenuLabels,
+    current_path: &str,
+) -> Vec<NavItem> {
+    let mut sorted = menu_items.to_vec();
+    sorted.sort_by_key(|item| item.order);
+
+    sorted
+        .iter()
+        .filter_map(|item| {
+            let label = labels.get(&item.id).map(str::to_string);
+
+            if let Some(url) = item.url.clone() {
+                return Some(NavItem {
+                    label: label.unwrap_or_else(|| pretty_url(&url)),
+                    href: url,
+                    active: false,
+                    external: true,
+                });
+            }
+
+            let page_id = item.page_id.as_ref()?;
+            let (page, _page_content) = pages.active(page_id)?;
+            let href = format!("/{}", page.slug);
+
+            Some(NavItem {
+                label: label.unwrap_or_else(|| page.title.clone()),
+                active: current_path.trim_end_matches('/') == href.trim_end_matches('/'),
+                href,
+                external: false,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use canopie_utils::models::{Menu, MenuItem, MenuItemLabel};
+
+use crate::data::{PageWithContent, SiteData};
+
+/// Whether an `active` column is set.
+pub fn is_active(active: i32) -> bool {
+    active != 0
+}
+
+/// Drops inactive menus, inactive menu items and the items nested under an
+/// inactive item, so themes that list a menu's items flat do not show
+/// children whose parent has been switched off.
+pub fn active_menus(menus: Vec<(Menu, Vec<MenuItem>)>) -> Vec<(Menu, Vec<MenuItem>)> {
+    menus
+        .into_iter()
+        .filter(|(menu, _)| is_active(menu.active))
+        .map(|(menu, items)| {
+            let reachable: Vec<bool> = items
+                .iter()
+                .map(|item| is_reachable(item, &items))
+                .collect();
+            let items = items
+                .into_iter()
+                .zip(reachable)
+                .filter_map(|(item, reachable)| reachable.then_some(item))
+                .collect();
+            (menu, items)
+        })
+        .collect()
+}
+
+/// Whether `item` and every item above it through `parent_id` are active.
+/// A `parent_id` that loops back on itself counts as unreachable.
+fn is_reachable(item: &MenuItem, items: &[MenuItem]) -> bool {
+    let mut current = Some(item);
+
+    for _ in 0..=items.len() {
+        let Some(item) = current else {
+            return true;
+        };
+        if !is_active(item.active) {
+            return false;
+        }
+        current = item
+            .parent_id
+            .as_deref()
+            .and_then(|parent_id| items.iter().find(|parent| parent.id == parent_id));
+    }
+
+    false
+}
+
+/// Every page the given menus link to, loaded with a single
+/// [`SiteData::pages_by_ids`] call instead of one lookup per item.
+#[derive(Debug, Clone, Default)]
+pub struct MenuPages {
+    pages: HashMap<String, PageWithContent>,
+}
+
+impl MenuPages {
+    pub fn load<D: SiteData + ?Sized>(data: &D, menus: &[(Menu, Vec<MenuItem>)]) -> Self {
+        let mut page_ids: Vec<String> = menus
+            .iter()
+            .flat_map(|(_menu, items)| items)
+            .filter_map(|item| item.page_id.clone())
+            .collect();
+        page_ids.sort();
+        page_ids.dedup();
+
+        if page_ids.is_empty() {
+            return Self::default();
+        }
+
+        let pages = data
+            .pages_by_ids(&page_ids)
+            .into_iter()
+            .map(|(page, content)| (page.id.clone(), (page, content)))
+            .collect();
+
+        Self { pages }
+    }
+
+    pub fn get(&self, page_id: &str) -> Option<&PageWithContent> {
+        self.pages.get(page_id)
+    }
+
+    /// Like [`MenuPages::get`], but only for active pages.
+    pub fn active(&self, page_id: &str) -> Option<&PageWithContent> {
+        self.get(page_id)
+            .filter(|(page, _content)| is_active(page.active))
+    }
+}
+
+/// The labels menu items have been given for one language.
+#[derive(Debug, Clone, Default)]
+pub struct MenuLabels {
+    labels: HashMap<String, String>,
+}
+
+impl MenuLabels {
+    /// Keeps the non-empty labels written for `language`.
+    pub fn for_language(labels: Vec<MenuItemLabel>, language: Option<&str>) -> Self {
+        let Some(language) = language else {
+            return Self::default();
+        };
+
+        let labels = labels
+            .into_iter()
+            .filter(|label| label.language.eq_ignore_ascii_case(language))
+            .filter(|label| !label.label.trim().is_empty())
+            .map(|label| (label.menu_item_id, label.label.trim().to_string()))
+            .collect();
+
+        Self { labels }
+    }
+
+    pub fn get(&self, menu_item_id: &str) -> Option<&str> {
+        self.labels.get(menu_item_id).map(String::as_str)
+    }
+}
+
+/// A URL shortened for display, e.g. `https://www.bandcamp.com/artist/`
+/// becomes `bandcamp.com/artist`.
+pub fn pretty_url(url: &str) -> String {
+    let url = url.trim();
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_scheme = without_scheme
+        .strip_prefix("mailto:")
+        .unwrap_or(without_scheme);
+    let without_www = without_scheme
+        .strip_prefix("www.")
+        .unwrap_or(without_scheme);
+    let without_query = without_www.split(['?', '#']).next().unwrap_or(without_www);
+    let pretty = without_query.trim_end_matches('/');
+
+    if pretty.is_empty() {
+        url.to_string()
+    } else {
+        pretty.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::data::{
+        MemorySiteData,
+        test_support::{self, menu_item, page, row},
+    };
+
+    fn menu(id: &str, active: i32) -> Menu {
+        row(test_support::menu(id, id, active))
+    }
+
+    fn item(id: &str, parent_id: Option<&str>, page_id: &str, active: i32) -> MenuItem {
+        row(menu_item(id, "menu", parent_id, Some(page_id), active))
+    }
+
+    fn item_ids(menus: &[(Menu, Vec<MenuItem>)]) -> Vec<(&str, Vec<&str>)> {
+        menus
+            .iter()
+            .map(|(menu, items)| {
+                let items = items.iter().map(|item| item.id.as_str()).collect();
+                (menu.id.as_str(), items)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn active_menus_drops_inactive_menus_and_items() {
+        let menus = active_menus(vec![
+            (
+                menu("main", 1),
+                vec![
+                    item("shown", None, "page-a", 1),
+                    item("hidden", None, "page-b", 0),
+                ],
+            ),
+            (menu("retired", 0), vec![item("orphan", None, "page-a", 1)]),
+        ]);
+
+        assert_eq!(item_ids(&menus), vec![("main", vec!["shown"])]);
+    }
+
+    #[test]
+    fn active_menus_drops_the_children_of_inactive_items() {
+        let menus = active_menus(vec![(
+            menu("main", 1),
+            vec![
+                item("about", None, "page-a", 1),
+                item("team", Some("about"), "page-a", 1),
+                item("archive", None, "page-b", 0),
+                item("2019", Some("archive"), "page-b", 1),
+                item("2019-tour", Some("2019"), "page-b", 1),
+            ],
+        )]);
+
+        assert_eq!(item_ids(&menus), vec![("main", vec!["about", "team"])]);
+    }
+
+    #[test]
+    fn menu_items_in_a_parent_loop_are_dropped() {
+        let menus = active_menus(vec![(
+            menu("main", 1),
+            vec![
+                item("a", Some("b"), "page-a", 1),
+                item("b", Some("a"), "page-b", 1),
+            ],
+        )]);
+
+        assert_eq!(item_ids(&menus), vec![("main", vec![])]);
+    }
+
+    #[test]
+    fn menu_pages_only_hands_out_active_pages() {
+        let site: MemorySiteData = serde_json::from_value(json!({
+            "pages": [page("page-live", "page-live", 1), page("page-draft", "page-draft", 0)],
+        }))
+        .unwrap();
+        let menus = vec![(
+            menu("main", 1),
+            vec![
+                item("live", None, "page-live", 1),
+                item("draft", None, "page-draft", 1),
+            ],
+        )];
+
+        let pages = MenuPages::load(&site, &menus);
+
+        assert!(pages.active("page-live").is_some());
+        assert!(pages.active("page-draft").is_none());
+        assert!(pages.get("page-draft").is_some());
+        assert!(pages.active("page-unknown").is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use canopie_utils::{
+    components::{Formatter, build_components},
+    header::Header,
+    models::{Menu, MenuItem, Page, PageContent},
+    renderer::{PageResponse, PageResult},
+};
+use maud::Markup;
+
+use crate::{
+    content::parse_page_content,
+    data::SiteData,
+    diagnostics::Diagnostics,
+    menu::{MenuLabels, MenuPages, active_menus, is_active},
+};
+
+/// What a theme loads before rendering a path: the active page at that path,
+/// the active menus with the pages they link to, and the menu labels in the
+/// page's language (or the project's default language when there is no page).
+pub struct PageContext {
+    /// `None` when the path has no active page with content.
+    pub page: Option<(Page, PageContent)>,
+    pub menus: Vec<(Menu, Vec<MenuItem>)>,
+    pub menu_pages: MenuPages,
+    pub labels: MenuLabels,
+}
+
+impl PageContext {
+    pub fn load<D: SiteData + ?Sized>(data: &D, website_id: &str, path: &str) -> Self {
+        let page = data
+            .page(website_id, path)
+            .filter(|(page, _page_content)| is_active(page.active))
+            .and_then(|(page, page_content)| page_content.map(|page_content| (page, page_content)));
+        let language = match &page {
+            Some((_page, page_content)) => Some(page_content.language.clone()),
+            None => data.default_language(website_id),
+        };
+        let labels =
+            MenuLabels::for_language(data.menu_item_labels(website_id), language.as_deref());
+
+        let menus = active_menus(data.menus(website_id));
+        let menu_pages = MenuPages::load(data, &menus);
+
+        Self {
+            page,
+            menus,
+            menu_pages,
+            labels,
+        }
+    }
+
+    /// The active menu named `Main`.
+    pub fn main_menu(&self) -> Option<&(Menu, Vec<MenuItem>)> {
+        self.menus.iter().find(|menu| menu.0.name == "Main")
+    }
+}
+
+/// Parses a page's content and builds its components, adding the assets the
+/// components need to `formatter`.
+pub fn render_page_content(
+    page_id: &str,
+    page_content: PageContent,
+    formatter: &Formatter,
+    diagnostics: &Diagnostics,
+) -> Markup {
+    let content = parse_page_content(page_id, page_content, diagnostics);
+    let (components, content_formatter) = build_components(content, None, Some(formatter.clone()));
+    formatter.add_assets_from(&content_formatter);
+
+    components
+}
+
+/// Moves a response's assets and title into `headers` and returns its
+/// result, with the diagnostics overlay in dev builds.
+pub fn finish_response(
+    response: PageResponse,
+    theme_id: &str,
+    headers: &mut Header,
+    diagnostics: &Diagnostics,
+) -> RenderOutcome {
+    let assets = response.formatter.collect_assets(Some(theme_id));
+    headers.add_assets(assets);
+    headers.set_title(response.title.as_str());
+
+    diagnostics.finish(response.result).into()
+}
+
+/// What a theme rendered for a path.
+///
+/// Like [`PageResult`], but tells a website that is not set up for its theme
+/// apart from a page that does not exist. Hosts that only know
+/// [`PageResult`] get the placeholder as [`PageResult::NotFound`].
+#[derive(Debug)]
+pub enum RenderOutcome {
+    Found(Markup),
+    NotFound(Markup),
+    /// The website lacks something the theme needs to render any page,
+    /// such as the menu its layout is built from.
+    NotConfigured(Markup),
+}
+
+impl RenderOutcome {
+    pub fn into_markup(self) -> Markup {
+        match self {
+            Self::Found(markup) | Self::NotFound(markup) | Self::NotConfigured(markup) => markup,
+        }
+    }
+}
+
+impl From<PageResult> for RenderOutcome {
+    fn from(result: PageResult) -> Self {
+        match result {
+            PageResult::Found(markup) => Self::Found(markup),
+            PageResult::NotFound(markup) => Self::NotFound(markup),
+        }
+    }
+}
+
+impl From<RenderOutcome> for PageResult {
+    fn from(outcome: RenderOutcome) -> Self {
+        match outcome {
+            RenderOutcome::Found(markup) => Self::Found(markup),
+            RenderOutcome::NotFound(markup) | RenderOutcome::NotConfigured(markup) => {
+                Self::NotFound(markup)
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use canopie_utils::themes::{GetThemeOverview, ThemeOverview, ThemeReference};
+#[cfg(feature = "embed")]
+use canopie_utils::{
+    renderer::{GetThemeRenderer, ThemeRenderer},
+    resource::embed::{Resources, ThemeResource},
+};
+#[cfg(feature = "embed")]
+use include_dir::Dir;
+use serde_json::Value;
+
+#[cfg(feature = "embed")]
+use crate::content::RenderMode;
+use crate::{
+    config::{FieldError, GetThemeConfig, default_config, upgrade_config, validate_config},
+    data::{RenderSite, SiteRenderer},
+    schema::dropdowns_without_choices,
+};
+
+/// Adds a theme crate's themes to a registry. Every theme crate exports one
+/// as `register`.
+pub type RegisterThemes = fn(ThemeRegistry) -> ThemeRegistry;
+
+/// A theme known to the registry and the capabilities it has registered.
+pub struct ThemeEntry {
+    pub id: String,
+    pub overview: Option<fn() -> ThemeOverview>,
+    pub reference: Option<fn() -> ThemeReference>,
+    pub validate_config: Option<fn(&Value) -> Result<(), Vec<FieldError>>>,
+    pub upgrade_config: Option<fn(Value) -> Value>,
+    pub default_config: Option<fn() -> Value>,
+    pub render_site: Option<SiteRenderer>,
+    #[cfg(feature = "embed")]
+    pub renderer: Option<fn() -> ThemeRenderer>,
+    /// Like `renderer`, but renders pages as CMS previews.
+    #[cfg(feature = "embed")]
+    pub preview_renderer: Option<fn() -> ThemeRenderer>,
+    #[cfg(feature = "embed")]
+    pub resources: Option<fn(&mut Resources) -> Result<(), String>>,
+    #[cfg(feature = "embed")]
+    pub static_dir: Option<fn() -> Dir<'static>>,
+}
+
+/// A renderer for the host that renders the theme's pages as CMS previews.
+#[cfg(feature = "embed")]
+fn preview_renderer<T: GetThemeOverview + RenderSite>() -> ThemeRenderer {
+    ThemeRenderer {
+        name: T::get_theme_overview().id,
+        build_content: |pool, headers, website, path| {
+            T::render_site(pool, headers, website, path, RenderMode::Preview).into()
+        },
+    }
+}
+
+impl ThemeEntry {
+    fn new(id: String) -> Self {
+        Self {
+            id,
+            overview: None,
+            reference: None,
+            validate_config: None,
+            upgrade_config: None,
+            default_config: None,
+            render_site: None,
+            #[cfg(feature = "embed")]
+            renderer: None,
+            #[cfg(feature = "embed")]
+            preview_renderer: None,
+            #[cfg(feature = "embed")]
+            resources: None,
+            #[cfg(feature = "embed")]
+            static_dir: None,
+        }
+    }
+
+    /// Whether every capability available in this build has been registered.
+    pub fn is_complete(&self) -> bool {
+        let complete = self.overview.is_some()
+            && self.reference.is_some()
+            && self.validate_config.is_some()
+            && self.upgrade_config.is_some()
+            && self.default_config.is_some()
+            && self.render_site.is_some();
+
+        #[cfg(feature = "embed")]
+        let complete = complete
+            && self.renderer.is_some()
+            && self.preview_renderer.is_some()
+            && self.resources.is_some()
+            && self.static_dir.is_some();
+
+        complete
+    }
+}
+
+/// Themes keyed by theme id.
+///
+/// Each theme crate registers its own themes through its `register` function,
+/// which calls [`ThemeRegistry::register`] and, with the `embed` feature, the
+/// renderer and resource methods. The per-capability methods exist so that a
+/// partial registration is representable and can be caught by
+/// [`ThemeRegistry::incomplete`].
+#[derive(Default)]
+pub struct ThemeRegistry {
+    entries: Vec<ThemeEntry>,
+    fallback: Option<String>,
+}
+
+impl ThemeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entry_mut(&mut self, id: String) -> &mut ThemeEntry {
+        let index = match self.entries.iter().position(|entry| entry.id == id) {
+            Some(index) => index,
+            None => {
+                self.entries.push(ThemeEntry::new(id));
+                self.entries.len() - 1
+            }
+        };
+
+        &mut self.entries[index]
+    }
+
+    /// Adds a theme with its overview, config handling and site renderer.
+    ///
+    /// # Panics
+    ///
+    /// When a theme with the same id is already registered, as two crates
+    /// claiming one id would otherwise silently overwrite each other. Also
+    /// when one of its dropdown fields has no choices, which means a field
+    /// name passed to [`crate::schema::with_dropdown_options`] has drifted from the config
+    /// struct.
+    pub fn register<T: GetThemeOverview + GetThemeConfig + RenderSite>(self) -> Self {
+        let overview = T::get_theme_overview();
+        let id = overview.id.clone();
+        assert!(
+            self.get(id.as_str()).is_none(),
+            "theme `{}` is registered twice",
+            id
+        );
+
+        let missing: Vec<String> = std::iter::once(&overview.config)
+            .chain(
+                overview
+                    .page_themes
+                    .iter()
+                    .map(|page_theme| &page_theme.config),
+            )
+            .flat_map(|schema| dropdowns_without_choices(schema))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "theme `{}` has dropdowns without choices: {}",
+            id,
+            missing.join(", ")
+        );
+
+        self.with_overview::<T>()
+            .with_config::<T>()
+            .with_site_renderer::<T>()
+    }
+
+    pub fn with_overview<T: GetThemeOverview>(mut self) -> Self {
+        let entry = self.entry_mut(T::get_theme_overview().id);
+        entry.overview = Some(T::get_theme_overview);
+        entry.reference = Some(T::get_reference);
+        self
+    }
+
+    pub fn with_config<T: GetThemeOverview + GetThemeConfig>(mut self) -> Self {
+        let entry = self.entry_mut(T::get_theme_overview().id);
+        entry.validate_config = Some(validate_config::<T>);
+        entry.upgrade_config = Some(upgrade_config::<T>);
+        entry.default_config = Some(default_config::<T>);
+        self
+    }
+
+    pub fn with_site_renderer<T: GetThemeOverview + RenderSite>(mut self) -> Self {
+        self.entry_mut(T::get_theme_overview().id).render_site = Some(T::render_site);
+        self
+    }
+
+    #[cfg(feature = "embed")]
+    pub fn with_renderer<T: GetThemeOverview + GetThemeRenderer + RenderSite>(mut self) -> Self {
+        let entry = self.entry_mut(T::get_theme_overview().id);
+        entry.renderer = Some(T::get_theme_renderer);
+        entry.preview_renderer = Some(preview_renderer::<T>);
+        self
+    }
+
+    #[cfg(feature = "embed")]
+    pub fn with_resources<T: GetThemeOverview + ThemeResource>(mut self) -> Self {
+        let entry = self.entry_mut(T::get_theme_overview().id);
+        entry.resources = Some(T::attach_theme_resources);
+        entry.static_dir = Some(T::get_theme_resources);
+        self
+    }
+
+    /// Marks a theme as the one used for unknown theme ids.
+    pub fn with_fallback<T: GetThemeOverview>(mut self) -> Self {
+        self.fallback = Some(T::get_theme_overview().id);
+        self
+    }
+
+    pub fn entries(&self) -> &[ThemeEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, theme_id: &str) -> Option<&ThemeEntry> {
+        self.entries.iter().find(|entry| entry.id == theme_id)
+    }
+
+    /// The fallback theme, if one was marked and it is registered.
+    pub fn fallback(&self) -> Option<&ThemeEntry> {
+        self.fallback
+            .as_deref()
+            .and_then(|theme_id| self.get(theme_id))
+    }
+
+    /// Ids of themes that registered some capabilities but not all of them.
+    pub fn incomplete(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_complete())
+            .map(|entry| entry.id.as_str())
+            .collect()
+    }
+}
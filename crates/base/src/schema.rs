@@ -0,0 +1,88 @@
+use canopie_utils::themes::PropertySchema;
+use serde_json::{Value, json};
+
+/// A fixed set of values for a `SelectDropdown` config field.
+///
+/// Implemented by the enums that back dropdown fields, so the stored value,
+/// the label in the admin UI and the Rust type cannot drift apart.
+pub trait DropdownOptions: Sized {
+    /// Every option as `(stored value, label)`, in display order.
+    fn options() -> Vec<(&'static str, &'static str)>;
+}
+
+/// Adds the options of `O` to the dropdown field named `field` in `schema`,
+/// as `meta.options.choices`.
+///
+/// A `field` the schema does not have is logged and the schema is returned
+/// unchanged. Registering the theme then fails on the dropdown left without
+/// choices, see [`dropdowns_without_choices`].
+pub fn with_dropdown_options<O: DropdownOptions>(
+    mut schema: Vec<PropertySchema>,
+    field: &str,
+) -> Vec<PropertySchema> {
+    let Some(property) = schema.iter_mut().find(|property| property.field == field) else {
+        tracing::error!(
+            "Config schema has no field `{}` for dropdown options",
+            field
+        );
+        return schema;
+    };
+
+    match dropdown_property::<O>(property) {
+        Ok(dropdown) => *property = dropdown,
+        Err(error) => tracing::error!("Could not add dropdown options to `{}`: {}", field, error),
+    }
+
+    schema
+}
+
+fn dropdown_property<O: DropdownOptions>(
+    property: &PropertySchema,
+) -> Result<PropertySchema, String> {
+    let choices: Vec<Value> = O::options()
+        .into_iter()
+        .map(|(value, text)| json!({ "text": text, "value": value }))
+        .collect();
+
+    let mut value = serde_json::to_value(property).map_err(|error| error.to_string())?;
+    let meta = value
+        .get_mut("meta")
+        .and_then(Value::as_object_mut)
+        .ok_or("the field has no meta object")?;
+
+    let options = meta.entry("options").or_insert_with(|| json!({}));
+    if !options.is_object() {
+        *options = json!({});
+    }
+    options["choices"] = Value::from(choices);
+
+    serde_json::from_value(value).map_err(|error| error.to_string())
+}
+
+/// The `SelectDropdown` fields in `schema` that have no choices to pick from.
+pub fn dropdowns_without_choices(schema: &[PropertySchema]) -> Vec<String> {
+    schema
+        .iter()
+        .filter(|property| {
+            let Ok(value) = serde_json::to_value(property) else {
+                return false;
+            };
+            let has_choices = value["meta"]["options"]["choices"]
+                .as_array()
+                .is_some_and(|choices| !choices.is_empty());
+
+            !has_choices && mentions(&value, "SelectDropdown")
+        })
+        .map(|property| property.field.clone())
+        .collect()
+}
+
+/// Whether `text` appears as a string anywhere in `value`.
+fn mentions(value: &Value, text: &str) -> bool {
+    match value {
+        Value::String(string) => string == text,
+        Value::Array(values) => values.iter().any(|value| mentions(value, text)),
+        Value::Object(map) => map.values().any(|value| mentions(value, text)),
+        _ => false,
+    }
+}
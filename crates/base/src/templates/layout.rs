@@ -0,0 +1,55 @@
+use canopie_utils::models::Website;
+use maud::{Markup, html};
+
+use crate::{BaseThemeConfig, NavItem};
+
+pub fn base_layout(
+    config: &BaseThemeConfig,
+    website: &Website,
+    page_title: &str,
+    nav_items: &[NavItem],
+    content: Markup,
+) -> Markup {
+    let style_vars = format!(
+        "--base-text: {}; --base-surface: {}; --base-accent: {};",
+        config.text_color, config.surface_color, config.primary_color
+    );
+
+    html! {
+        body class="base" style=(style_vars) {
+            header class="base-header" {
+                div class="base-container base-header-inner" {
+                    a class="base-brand" href="/" { (website.title) }
+                    @if !nav_items.is_empty() {
+                        nav class="base-nav" {
+                            @for item in nav_items {
+                                @if item.external {
+                                    a class="base-nav-link" href=(item.href) target="_blank" rel="noopener noreferrer" { (item.label) }
+                                } @else {
+                                    a class=(nav_link_class(item.active)) href=(item.href) { (item.label) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            main class="base-main" {
+                div class="base-container" {
+                    h1 class="base-title" { (page_title) }
+                    div class="base-content" { (content) }
+                }
+            }
+            footer class="base-footer" {
+                div class="base-container" { (website.title) }
+            }
+        }
+    }
+}
+
+fn nav_link_class(active: bool) -> &'static str {
+    if active {
+        "base-nav-link is-active"
+    } else {
+        "base-nav-link"
+    }
+}
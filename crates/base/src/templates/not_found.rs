@@ -0,0 +1,21 @@
+use canopie_utils::{components::Formatter, models::Website, renderer::PageResponse};
+use maud::html;
+
+use crate::templates::layout::base_layout;
+use crate::{BaseThemeConfig, NavItem};
+
+pub fn base_not_found(
+    config: &BaseThemeConfig,
+    website: &Website,
+    nav_items: &[NavItem],
+    formatter: Formatter,
+) -> PageResponse {
+    let content = html! {
+        p { "That page is missing or unpublished." }
+        p { a href="/" { "Back home" } }
+    };
+
+    let body = base_layout(config, website, "Page not found", nav_items, content);
+
+    PageResponse::not_found("Page not found".to_string(), body, formatter)
+}
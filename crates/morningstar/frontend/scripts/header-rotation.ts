@@ -1,6 +1,6 @@
 function updateHeaderRotation() {
 	// const header = document.querySelector<HTMLElement>("section#band::after");
-	const bandSection = document.querySelector<HTMLElement>("section#band");
+	const bandSection = document.querySelector<HTMLElement>("section.section-style-band");
 	if (!bandSection) return;
 
 	const header = document.querySelector<HTMLElement>(".band-header");
@@ -1,3 +1,4 @@
+use canopie_themes_base::css::background_image;
 use canopie_utils::{
     components::{Asset, AssetOptions, Formatter},
     types::tiptap::TipTapNode,
@@ -15,16 +16,17 @@ pub fn album_cover(content: &TipTapNode, formatter: &Formatter) -> Markup {
 
     formatter.add_asset(Asset::Style(AssetOptions::new("deco-cd")));
 
-    let src_str = src.unwrap_or("");
-
-    println!("Album cover source: {}", src_str);
+    let src_str = src.unwrap_or_else(|| {
+        tracing::warn!("Album cover has no src");
+        ""
+    });
 
     let re = Regex::new(r"^/_f/[^/]+").unwrap();
     let src_path = re.replace(src_str, "/_f");
 
     html! {
         div class="deco cd" {
-            div class="cd-front" style={"background-image: url('"(src_path)"'"} {}
+            div class="cd-front" style=[background_image(&src_path)] {}
             div class="cd-case" {}
 
         }
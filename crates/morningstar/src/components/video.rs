@@ -4,9 +4,32 @@ use canopie_utils::{
 };
 use maud::{Markup, html};
 
-use crate::components::bordered::{BorderWidth, Counts, Props, bordered_component};
+use crate::{
+    BorderIntensity, MorningStarConfig,
+    components::bordered::{BorderWidth, Counts, bordered_component},
+};
+
+/// The `youtube` component for `config`'s border intensity. Formatter
+/// components can't see the theme config, so each intensity gets its own fn.
+pub fn youtube_component(config: &MorningStarConfig) -> fn(&TipTapNode, &Formatter) -> Markup {
+    match config.border_intensity {
+        BorderIntensity::Subtle => {
+            |content, formatter| youtube(content, formatter, BorderIntensity::Subtle)
+        }
+        BorderIntensity::Normal => {
+            |content, formatter| youtube(content, formatter, BorderIntensity::Normal)
+        }
+        BorderIntensity::Strong => {
+            |content, formatter| youtube(content, formatter, BorderIntensity::Strong)
+        }
+    }
+}
 
-pub fn youtube(content: &TipTapNode, formatter: &Formatter) -> Markup {
+fn youtube(
+    content: &TipTapNode,
+    formatter: &Formatter,
+    border_intensity: BorderIntensity,
+) -> Markup {
     let src_id: Option<&str> = content
         .attrs
         .as_ref() // Option<&Value>
@@ -16,12 +39,12 @@ pub fn youtube(content: &TipTapNode, formatter: &Formatter) -> Markup {
 
     formatter.add_asset(Asset::Script(AssetOptions::new("youtube")));
 
-    let mut bordered_props = Props::default();
-    bordered_props.seed = "src_id".to_string();
-    bordered_props.intensity = (0.05, 0.05);
-    bordered_props.goo = 1.0;
-    bordered_props.id_card = "bigger".to_string();
-    bordered_props.color = "#e5aaa7".to_string();
+    // Components can't see the theme config; the page body sets the accent.
+    let mut bordered_props = border_intensity.bordered_props(
+        "var(--morningstar-accent, #e5aaa7)",
+        "src_id",
+        (0.05, 0.05),
+    );
     bordered_props.counts = Some(Counts::XY(30, 15));
     bordered_props.border_width = BorderWidth::Single(0.2);
     bordered_props.width_variance = (1.3, 1.2);
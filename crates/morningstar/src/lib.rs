@@ -1,11 +1,18 @@
 use canopie_macros::{ThemeConfig, theme_defaults};
+use canopie_themes_base::{
+    config::{ConfigMigration, GetThemeConfig, resolve_config},
+    content::RenderMode,
+    data::{RenderSite, SiteData},
+    diagnostics::Diagnostics,
+    menu::active_menus,
+    page::{RenderOutcome, finish_response},
+    registry::ThemeRegistry,
+    schema::with_dropdown_options,
+};
 use canopie_utils::{
     components::{Asset, AssetOptions, Formatter},
-    db::PgPool,
     header::Header,
     models::Website,
-    renderer::PageResult,
-    theme_utils::get_menus,
     themes::{
         self, GetThemeOverview, PageThemeOverview, ThemeOverview, ThemeRestriction, ThemeSchema,
     },
@@ -18,54 +25,139 @@ use canopie_utils::{
 #[cfg(feature = "embed")]
 use include_dir::Dir;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::{
-    components::{album_cover::album_cover, video::youtube},
+    components::{
+        album_cover::album_cover, bordered::Props as BorderedProps, video::youtube_component,
+    },
     templates::{
-        not_found::morningstar_not_found,
+        not_found::morningstar_not_configured,
         single::SinglePageConfig,
         spa::{SpaPageConfig, build_content_for_menu_pages},
     },
 };
 
+pub use crate::options::{BorderIntensity, PageStylesheets, SectionStyle};
+
 mod components;
+mod options;
 mod sections;
 mod templates;
 
-pub fn morningstar(
-    pool: &PgPool,
+pub fn morningstar<D: SiteData + ?Sized>(
+    data: &D,
     headers: &mut Header,
     website: Website,
     path: &str,
-) -> PageResult {
-    let menus = get_menus(pool, &website.id);
+    mode: RenderMode,
+) -> RenderOutcome {
+    let theme_config = resolve_theme_config(&website);
+    let diagnostics = Diagnostics::for_mode(mode);
+    let menus = active_menus(data.menus(&website.id));
 
     let main_menu = menus.iter().find(|menu| menu.0.name == "Main");
     let mut formatter = Formatter::default(Some(website.clone()));
 
     formatter.add_asset(Asset::Style(AssetOptions::new("index")));
 
-    formatter.insert("youtube", youtube);
+    formatter.insert("youtube", youtube_component(&theme_config));
     formatter.insert("albumCover", album_cover);
 
-    let page_response = match main_menu {
-        Some(menu) => {
-            build_content_for_menu_pages(pool, path, &website.id, menu, formatter, headers)
-        }
-        None => morningstar_not_found(formatter, headers),
+    let Some(menu) = main_menu else {
+        let response = morningstar_not_configured(
+            &theme_config,
+            &website.id,
+            formatter,
+            headers,
+            &diagnostics,
+        );
+        let outcome = finish_response(response, "morningstar", headers, &diagnostics);
+        return RenderOutcome::NotConfigured(outcome.into_markup());
     };
 
-    let assets = page_response.formatter.collect_assets(Some("morningstar"));
+    let page_response = build_content_for_menu_pages(
+        data,
+        &theme_config,
+        path,
+        &website.id,
+        menu,
+        formatter,
+        headers,
+        &diagnostics,
+    );
 
-    headers.add_assets(assets);
-    headers.set_title(page_response.title.as_str());
+    finish_response(page_response, "morningstar", headers, &diagnostics)
+}
 
-    page_response.result
+fn resolve_theme_config(website: &Website) -> MorningStarConfig {
+    resolve_config::<ThemeMorningStar>(website.theme_config.as_ref())
 }
 
 #[theme_defaults]
 #[derive(Serialize, Deserialize, Debug, ThemeConfig)]
-pub struct MorningStarConfig {}
+pub struct MorningStarConfig {
+    #[theme(
+        interface = "SelectColor",
+        width = "Half",
+        default = "#e5aaa7",
+        regex = "^#([A-Fa-f0-9]{6})$"
+    )]
+    pub accent_color: String,
+    #[theme(interface = "SelectDropdown", default = "normal", width = "Half")]
+    pub border_intensity: BorderIntensity,
+    #[theme(interface = "Input", default = "info@morningstarmusic.club")]
+    pub contact_email: String,
+    #[theme(
+        interface = "InputText",
+        default = "For booking, press enquiries and collaborations please contact: "
+    )]
+    pub contact_text: String,
+    #[theme(interface = "Checkbox", default = true, width = "Half")]
+    pub show_contact_in_menu: bool,
+    #[theme(interface = "Input", default = "Contact", width = "Half")]
+    pub contact_label: String,
+    #[theme(interface = "FileImage")]
+    pub banner_image: Option<String>,
+    #[theme(interface = "FileImage")]
+    pub banner_secondary_image: Option<String>,
+    #[theme(interface = "FileImage")]
+    pub portrait_image: Option<String>,
+}
+
+impl Default for MorningStarConfig {
+    fn default() -> Self {
+        Self {
+            site_icon: None,
+            primary_color: "#e5aaa7".to_string(),
+            accent_color: "#e5aaa7".to_string(),
+            border_intensity: BorderIntensity::default(),
+            contact_email: "info@morningstarmusic.club".to_string(),
+            contact_text: "For booking, press enquiries and collaborations please contact: "
+                .to_string(),
+            show_contact_in_menu: true,
+            contact_label: "Contact".to_string(),
+            banner_image: None,
+            banner_secondary_image: None,
+            portrait_image: None,
+        }
+    }
+}
+
+impl MorningStarConfig {
+    /// Bordered props in the theme's accent colour, with `intensity` scaled
+    /// by the configured border intensity.
+    pub(crate) fn bordered_props(&self, seed: &str, intensity: (f64, f64)) -> BorderedProps {
+        self.border_intensity
+            .bordered_props(&self.accent_color, seed, intensity)
+    }
+
+    /// Sets the accent as a CSS custom property, for components rendered
+    /// through the formatter that cannot see the config.
+    pub(crate) fn style_vars(&self) -> String {
+        format!("--morningstar-accent: {};", self.accent_color)
+    }
+}
 
 pub struct ThemeMorningStar {}
 
@@ -76,16 +168,25 @@ impl GetThemeOverview for ThemeMorningStar {
             name: "Morning Star Music Club".to_string(),
             description: Some("Morning Star Music Club".to_string()),
             restricted: ThemeRestriction::Restricted(vec!["morningstar".to_string()]),
-            config: MorningStarConfig::schema(),
+            config: with_dropdown_options::<BorderIntensity>(
+                MorningStarConfig::schema(),
+                "border_intensity",
+            ),
             page_themes: vec![
                 PageThemeOverview {
                     name: "Home Segment".to_string(),
-                    config: SpaPageConfig::schema(),
+                    config: with_dropdown_options::<SectionStyle>(
+                        SpaPageConfig::schema(),
+                        "section_style",
+                    ),
                     default: false,
                 },
                 PageThemeOverview {
                     name: "Full Page".to_string(),
-                    config: SinglePageConfig::schema(),
+                    config: with_dropdown_options::<PageStylesheets>(
+                        SinglePageConfig::schema(),
+                        "stylesheet",
+                    ),
                     default: true,
                 },
             ],
@@ -93,6 +194,38 @@ impl GetThemeOverview for ThemeMorningStar {
     }
 }
 
+impl GetThemeConfig for ThemeMorningStar {
+    type Config = MorningStarConfig;
+
+    fn migrations() -> Vec<ConfigMigration> {
+        vec![accent_from_primary_color]
+    }
+}
+
+/// Config migration 1. Before `accent_color` existed the only colour a site
+/// could pick was the shared `primary_color`, so it becomes the accent of
+/// configs saved back then.
+fn accent_from_primary_color(mut value: Value) -> Value {
+    let primary_color = value.get("primary_color").cloned();
+
+    if let (Some(fields), Some(primary_color)) = (value.as_object_mut(), primary_color) {
+        fields.entry("accent_color").or_insert(primary_color);
+    }
+    value
+}
+
+impl RenderSite for ThemeMorningStar {
+    fn render_site(
+        data: &dyn SiteData,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+        mode: RenderMode,
+    ) -> RenderOutcome {
+        morningstar(data, headers, website, path, mode)
+    }
+}
+
 #[cfg(feature = "embed")]
 impl ThemeResource for ThemeMorningStar {
     fn get_theme_resources() -> Dir<'static> {
@@ -116,7 +249,22 @@ impl GetThemeRenderer for ThemeMorningStar {
     fn get_theme_renderer() -> ThemeRenderer {
         ThemeRenderer {
             name: Self::get_theme_overview().id,
-            build_content: morningstar,
+            build_content: |pool, headers, website, path| {
+                morningstar(pool, headers, website, path, RenderMode::Live).into()
+            },
         }
     }
 }
+
+#[cfg(not(feature = "embed"))]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry.register::<ThemeMorningStar>()
+}
+
+#[cfg(feature = "embed")]
+pub fn register(registry: ThemeRegistry) -> ThemeRegistry {
+    registry
+        .register::<ThemeMorningStar>()
+        .with_renderer::<ThemeMorningStar>()
+        .with_resources::<ThemeMorningStar>()
+}
@@ -1,9 +1,11 @@
 use std::io::Error;
 
+#[cfg(feature = "embed")]
+use canopie_themes_base::content::RenderMode;
+#[cfg(feature = "embed")]
 use canopie_themes_morningstar::morningstar;
-use canopie_utils::renderer::ThemeRenderer;
 #[cfg(feature = "embed")]
-use canopie_utils::theme_dev::run::run::dev_serve;
+use canopie_utils::{renderer::ThemeRenderer, theme_dev::run::run::dev_serve};
 #[cfg(feature = "embed")]
 use include_dir::{Dir, include_dir};
 
@@ -13,13 +15,11 @@ fn main() -> Result<(), Error> {
         let theme_dir: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/static");
         let s3_dir: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/s3");
 
-        let has_names = theme_dir.contains("images/names.webp");
-
-        println!("Has names: {}", has_names);
-
         let renderer = ThemeRenderer {
             name: String::from("morningstar"),
-            build_content: morningstar,
+            build_content: |pool, headers, website, path| {
+                morningstar(pool, headers, website, path, RenderMode::Live).into()
+            },
         };
 
         dev_serve(renderer, Some(theme_dir), Some(s3_dir))
@@ -27,7 +27,6 @@ fn main() -> Result<(), Error> {
 
     #[cfg(not(feature = "embed"))]
     {
-        println!("Embed feature is not enabled");
-        Ok(())
+        Err(Error::other("the dev server needs the embed feature"))
     }
 }
@@ -0,0 +1,207 @@
+use canopie_themes_base::schema::DropdownOptions;
+use include_dir::{Dir, include_dir};
+use serde::{Deserialize, Serialize};
+
+use crate::components::bordered::Props;
+
+static STYLES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/static/styles");
+
+/// How far the hand-drawn borders wobble away from a straight line.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BorderIntensity {
+    Subtle,
+    #[default]
+    Normal,
+    Strong,
+}
+
+impl BorderIntensity {
+    /// Multiplier applied to each border's own intensity.
+    pub fn factor(self) -> f64 {
+        match self {
+            BorderIntensity::Subtle => 0.5,
+            BorderIntensity::Normal => 1.0,
+            BorderIntensity::Strong => 1.6,
+        }
+    }
+
+    /// Bordered props drawn in `color`, with `intensity` scaled by
+    /// [`BorderIntensity::factor`].
+    pub(crate) fn bordered_props(self, color: &str, seed: &str, intensity: (f64, f64)) -> Props {
+        let factor = self.factor();
+
+        let mut bordered_props = Props::default();
+        bordered_props.seed = seed.to_string();
+        bordered_props.intensity = (intensity.0 * factor, intensity.1 * factor);
+        bordered_props.goo = 1.0;
+        bordered_props.id_card = "bigger".to_string();
+        bordered_props.color = color.to_string();
+        bordered_props
+    }
+}
+
+impl DropdownOptions for BorderIntensity {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("subtle", "Subtle"),
+            ("normal", "Normal"),
+            ("strong", "Strong"),
+        ]
+    }
+}
+
+/// How a page is presented as a section of the one-page layout.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionStyle {
+    #[default]
+    Default,
+    /// Text wrapped around the large title block.
+    Band,
+    /// Alternating video rows under a title image.
+    Vids,
+    /// Tilted title image over the content.
+    Music,
+    /// Dark text on a light panel.
+    Light,
+    /// Full-width background image with the content over it.
+    Image,
+}
+
+impl SectionStyle {
+    /// The style pages had before it could be chosen, picked by slug.
+    pub fn legacy_for_slug(slug: &str) -> Self {
+        match slug.trim_matches('/') {
+            "band" => SectionStyle::Band,
+            "vids" => SectionStyle::Vids,
+            "music" => SectionStyle::Music,
+            _ => SectionStyle::Default,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SectionStyle::Default => "default",
+            SectionStyle::Band => "band",
+            SectionStyle::Vids => "vids",
+            SectionStyle::Music => "music",
+            SectionStyle::Light => "light",
+            SectionStyle::Image => "image",
+        }
+    }
+}
+
+impl DropdownOptions for SectionStyle {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("default", "Default"),
+            ("band", "Band"),
+            ("vids", "Videos"),
+            ("music", "Music"),
+            ("light", "Light panel"),
+            ("image", "Background image"),
+        ]
+    }
+}
+
+/// Stylesheets the theme loads itself, which pages cannot pick.
+const THEME_STYLESHEETS: &[&str] = &[
+    "base",
+    "border-component",
+    "contact",
+    "deco-cd",
+    "home",
+    "index",
+    "menu",
+    "page-band",
+    "single-page",
+    "tailwind",
+];
+
+/// The stylesheets in `static/styles` a single page can add, by file stem.
+pub struct PageStylesheets;
+
+impl PageStylesheets {
+    /// The stylesheet pages had before it could be chosen, picked by slug.
+    pub fn legacy_for_slug(slug: &str) -> Option<&'static str> {
+        match slug.trim_matches('/') {
+            "liminal-zone" => Some("liminal-zone"),
+            _ => None,
+        }
+    }
+
+    /// The `/_canopie/static` URL of a stylesheet, if the theme ships it.
+    pub fn href(name: &str) -> Option<String> {
+        Self::names()
+            .into_iter()
+            .find(|(stem, _)| *stem == name)
+            .map(|(_, file)| format!("/_canopie/static/styles/{}", file))
+    }
+
+    fn names() -> Vec<(&'static str, &'static str)> {
+        let mut names: Vec<(&'static str, &'static str)> = STYLES
+            .files()
+            .filter(|file| {
+                file.path()
+                    .extension()
+                    .is_some_and(|extension| extension == "css")
+            })
+            .filter_map(|file| {
+                let stem = file.path().file_stem()?.to_str()?;
+                let name = file.path().file_name()?.to_str()?;
+                Some((stem, name))
+            })
+            .filter(|(stem, _)| !THEME_STYLESHEETS.contains(stem))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl DropdownOptions for PageStylesheets {
+    fn options() -> Vec<(&'static str, &'static str)> {
+        std::iter::once(("", "None")).chain(Self::names()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_stylesheets_link_to_the_static_files() {
+        assert_eq!(
+            PageStylesheets::href("liminal-zone").as_deref(),
+            Some("/_canopie/static/styles/liminal-zone.css")
+        );
+        assert_eq!(PageStylesheets::href("no-such-sheet"), None);
+    }
+
+    #[test]
+    fn theme_stylesheets_cannot_be_picked() {
+        for stem in THEME_STYLESHEETS {
+            assert!(
+                STYLES.get_file(format!("{}.css", stem)).is_some(),
+                "{}",
+                stem
+            );
+            assert_eq!(PageStylesheets::href(stem), None, "{}", stem);
+        }
+
+        let options = PageStylesheets::options();
+        assert_eq!(
+            options,
+            [("", "None"), ("liminal-zone", "liminal-zone.css")]
+        );
+    }
+
+    #[test]
+    fn only_the_liminal_zone_has_a_legacy_stylesheet() {
+        assert_eq!(
+            PageStylesheets::legacy_for_slug("/liminal-zone"),
+            Some("liminal-zone")
+        );
+        assert_eq!(PageStylesheets::legacy_for_slug("about"), None);
+    }
+}
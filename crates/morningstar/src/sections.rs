@@ -1,41 +1,71 @@
+use canopie_themes_base::css;
 use maud::{Markup, html};
 
-use crate::components::bordered::{
-    BorderWidth, Counts, Edge, Props as BorderedProps, bordered_component,
+use crate::{
+    MorningStarConfig, SectionStyle,
+    components::bordered::{BorderWidth, Counts, Edge, bordered_component},
+    templates::spa::SpaPageConfig,
 };
 
-fn main_menu() -> Markup {
+/// The hero artwork has a place for this many links. Further items are
+/// listed below it.
+const ARTWORK_SLOTS: usize = 5;
+
+/// Icons bundled in `static/images/menu`, used when a page has no icon of
+/// its own and its slug matches.
+const BUNDLED_MENU_ICONS: &[&str] = &["band", "vids", "music", "live", "contact"];
+
+/// A link in the hero menu, built from an item of the Main menu.
+#[derive(Debug, Clone)]
+pub struct HeroMenuItem {
+    pub label: String,
+    pub slug: String,
+    /// Uploaded icon from the page's `menu_icon` option.
+    pub icon: Option<String>,
+}
+
+fn main_menu(items: &[HeroMenuItem]) -> Markup {
+    let (in_artwork, below_artwork) = items.split_at(items.len().min(ARTWORK_SLOTS));
+
     html! {
         div class="menu" {
           div class="menu-bg" {}
           div class="menu-content"{
-            a href="/band" page-title="Band" {
-              div class="link band" { ("Band") }
-            }
-            a href="/vids" page-title="Vids" {
-              div class="link vids" { ("Vids") }
-            }
-            a href="/music" page-title="Music" {
-              div class="link music" { ("Music") }
+            @for (index, item) in in_artwork.iter().enumerate() {
+              a href=(format!("/{}", item.slug)) page-title=(item.label) {
+                div class=(menu_link_class(index, item)) style=[background_image(&item.icon)] {
+                  (item.label)
+                }
+              }
             }
-            // <!-- <a @click="scrollToSection('live')">
-            //   <div class="link live">Live</div>
-            // </a> -->
-            a href="/contact" page-title="Contact" {
-              div class="link contact"{ ("Contact") }
+          }
+        }
+        @if !below_artwork.is_empty() {
+          div class="menu-more" {
+            @for item in below_artwork {
+              a class="link-text" href=(format!("/{}", item.slug)) page-title=(item.label) {
+                (item.label)
+              }
             }
           }
         }
     }
 }
 
-pub fn section_home() -> Markup {
-    let mut bordered_props = BorderedProps::default();
-    bordered_props.seed = "homepage".to_string();
-    bordered_props.intensity = (0.1, 0.2);
-    bordered_props.goo = 1.0;
-    bordered_props.id_card = "bigger".to_string();
-    bordered_props.color = "#e5aaa7".to_string();
+fn menu_link_class(index: usize, item: &HeroMenuItem) -> String {
+    let slot = format!("link slot-{}", index + 1);
+
+    if item.icon.is_some() {
+        format!("{} icon-custom", slot)
+    } else if BUNDLED_MENU_ICONS.contains(&item.slug.as_str()) {
+        format!("{} icon-{}", slot, item.slug)
+    } else {
+        format!("{} link-text", slot)
+    }
+}
+
+pub fn section_home(config: &MorningStarConfig, menu_items: &[HeroMenuItem]) -> Markup {
+    let mut bordered_props = config.bordered_props("homepage", (0.1, 0.2));
     bordered_props.counts = Some(Counts::XY(20, 30));
     bordered_props.border_width = BorderWidth::Single(0.08);
     bordered_props.edges = vec![Edge::Bottom, Edge::Right];
@@ -46,7 +76,7 @@ pub fn section_home() -> Markup {
         960.0,
         None,
         html! {
-            (main_menu())
+            (main_menu(menu_items))
 
             div class="sm:h-40 md:h-80 flex items-center justify-center"{
               img src="/_canopie/static/images/names.webp" {}
@@ -58,8 +88,8 @@ pub fn section_home() -> Markup {
     html! {
         div class="home-container relative" {
             div class="banner-container"{
-                div class="banner morning-star" {}
-                div class="banner music-club" {}
+                div class="banner morning-star" style=[background_image(&config.banner_image)] {}
+                div class="banner music-club" style=[background_image(&config.banner_secondary_image)] {}
             }
             div class="hidden sm:block sm:max-w-md md:max-w-screen-sm absolute z-10" {
                 //DECOBORDER
@@ -70,23 +100,61 @@ pub fn section_home() -> Markup {
                 // }
             }
             div class="block sm:hidden absolute top-0 left-0 z-10 w-full" {
-                (main_menu())
+                (main_menu(menu_items))
             }
             div class="jesse h-full w-full" {
-                div class="jesse-image" {}
+                div class="jesse-image" style=[background_image(&config.portrait_image)] {}
             }
         }
     }
 }
 
-pub fn render_section(section: &str, content: Markup) -> Markup {
-    match section {
-        "music" => section_music(content),
-        "band" => section_band(content),
-        "vids" => section_vids(content),
-        _ => html! {
-            div class="section-default" {
+/// Overrides a stylesheet's default background image with an uploaded one.
+fn background_image(image: &Option<String>) -> Option<String> {
+    image.as_deref().and_then(css::background_image)
+}
+
+pub fn render_section(config: &SpaPageConfig, content: Markup) -> Markup {
+    let background = background_image(&config.section_background);
+
+    match config.section_style {
+        SectionStyle::Band => section_band(config, background, content),
+        SectionStyle::Vids => html! {
+            div class="section-vids" style=[background] {
+                div class="section-vids-content text-white container m-auto max-w-screen-md" {
+                    div class="page-title" style=[background_image(&config.title_image)] {}
+                    (content)
+                }
+            }
+        },
+        SectionStyle::Music => html! {
+            div class="section-music" style=[background] {
+                div class="section-about-content text-white container m-auto max-w-screen-md" {
+                    div class="page-title" style=[background_image(&config.title_image)] {}
+                    (content)
+                }
+            }
+        },
+        SectionStyle::Light => html! {
+            div class="section-light" style=[background] {
+                div class="section-light-content container m-auto max-w-screen-md" {
+                    (title_image(config))
+                    (content)
+                }
+            }
+        },
+        SectionStyle::Image => html! {
+            div class="section-image" style=[background] {
+                div class="section-image-content text-white container m-auto max-w-screen-md" {
+                    (title_image(config))
+                    (content)
+                }
+            }
+        },
+        SectionStyle::Default => html! {
+            div class="section-default" style=[background] {
                 div class="section-default-content" {
+                    (title_image(config))
                     (content)
                 }
             }
@@ -94,10 +162,10 @@ pub fn render_section(section: &str, content: Markup) -> Markup {
     }
 }
 
-fn section_band(content: Markup) -> Markup {
+fn section_band(config: &SpaPageConfig, background: Option<String>, content: Markup) -> Markup {
     html! {
-        div class="band-header" {}
-        div class="section-band" {
+        div class="band-header" style=[background_image(&config.title_image)] {}
+        div class="section-band" style=[background] {
             div class="big-block" {}
             div class="section-band-content text-white container m-auto max-w-screen-md" {
                 (content)
@@ -106,24 +174,85 @@ fn section_band(content: Markup) -> Markup {
     }
 }
 
-fn section_vids(content: Markup) -> Markup {
+/// The optional title image of the styles without one in their stylesheet.
+fn title_image(config: &SpaPageConfig) -> Markup {
     html! {
-        div class="section-vids" {
-            div class="section-vids-content text-white container m-auto max-w-screen-md" {
-                div class="page-title" {}
-                (content)
-            }
+        @if let Some(style) = background_image(&config.title_image) {
+            div class="page-title section-title-image" style=(style) {}
         }
     }
 }
 
-fn section_music(content: Markup) -> Markup {
-    html! {
-        div class="section-music" {
-            div class="section-about-content text-white container m-auto max-w-screen-md" {
-                div class="page-title" {}
-                (content)
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(slug: &str, icon: Option<&str>) -> HeroMenuItem {
+        HeroMenuItem {
+            label: slug.to_uppercase(),
+            slug: slug.to_string(),
+            icon: icon.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn items_beyond_the_artwork_are_listed_below_it() {
+        let items: Vec<HeroMenuItem> = (1..=ARTWORK_SLOTS + 2)
+            .map(|number| item(&format!("page-{}", number), None))
+            .collect();
+
+        let html = main_menu(&items).into_string();
+
+        for slot in 1..=ARTWORK_SLOTS {
+            assert!(
+                html.contains(&format!("link slot-{} ", slot)),
+                "slot {}",
+                slot
+            );
+        }
+        assert!(!html.contains(&format!("slot-{}", ARTWORK_SLOTS + 1)));
+
+        let (_artwork, below) = html.split_once(r#"<div class="menu-more">"#).unwrap();
+        for number in ARTWORK_SLOTS + 1..=ARTWORK_SLOTS + 2 {
+            assert!(below.contains(&format!(r#"href="/page-{}""#, number)));
         }
     }
+
+    #[test]
+    fn short_menus_fit_in_the_artwork() {
+        let html = main_menu(&[item("music", None)]).into_string();
+
+        assert!(!html.contains("menu-more"));
+    }
+
+    #[test]
+    fn uploaded_icons_win_over_bundled_ones() {
+        assert_eq!(
+            menu_link_class(0, &item("music", Some("/_f/icon.png"))),
+            "link slot-1 icon-custom"
+        );
+
+        let html = main_menu(&[item("music", Some("/_f/icon.png"))]).into_string();
+        assert!(html.contains("background-image: url('/_f/icon.png');"));
+    }
+
+    #[test]
+    fn bundled_icons_match_the_slug() {
+        assert_eq!(
+            menu_link_class(1, &item("vids", None)),
+            "link slot-2 icon-vids"
+        );
+        assert_eq!(
+            menu_link_class(4, &item("contact", None)),
+            "link slot-5 icon-contact"
+        );
+    }
+
+    #[test]
+    fn items_without_an_icon_show_their_label() {
+        assert_eq!(
+            menu_link_class(2, &item("press", None)),
+            "link slot-3 link-text"
+        );
+    }
 }
@@ -6,39 +6,40 @@ use canopie_utils::header::Header;
 use maud::{Markup, html};
 
 use crate::{
-    components::bordered::{BorderWidth, Counts, Edge, Props as BorderedProps, bordered_component},
-    sections::section_home,
+    MorningStarConfig, SectionStyle,
+    components::bordered::{BorderWidth, Counts, Edge, bordered_component},
+    sections::{HeroMenuItem, section_home},
 };
 
-pub fn home(content: Markup, headers: &mut Header) -> Markup {
+pub fn home(
+    config: &MorningStarConfig,
+    menu_items: &[HeroMenuItem],
+    content: Markup,
+    headers: &mut Header,
+) -> Markup {
     headers.add_asset(html!(link rel="stylesheet" href="/_canopie/static/styles/index.css";));
     html! {
-        body class="text-gray-900 font-sans" {
+        body class="text-gray-900 font-sans" style=(config.style_vars()) {
             main class="prose lg:prose-lg" {
-                (section_home())
+                (section_home(config, menu_items))
                 (content)
             }
-            (contact_section())
+            (contact_section(config))
         }
     }
 }
 
-pub fn home_section(path: &str, title: String, content: Markup) -> Markup {
+pub fn home_section(path: &str, title: String, style: SectionStyle, content: Markup) -> Markup {
     let id = path.replace("/", "_");
     html! {
-        section id=(id) page-title=(title) {
+        section id=(id) class=(format!("section-style-{}", style.as_str())) page-title=(title) {
             (content)
         }
     }
 }
 
-pub fn contact_section() -> Markup {
-    let mut bordered_props = BorderedProps::default();
-    bordered_props.seed = "contact".to_string();
-    bordered_props.intensity = (0.75, 0.75);
-    bordered_props.goo = 1.0;
-    bordered_props.id_card = "bigger".to_string();
-    bordered_props.color = "#e5aaa7".to_string();
+pub fn contact_section(config: &MorningStarConfig) -> Markup {
+    let mut bordered_props = config.bordered_props("contact", (0.75, 0.75));
     bordered_props.counts = Some(Counts::XY(30, 15));
     bordered_props.border_width = BorderWidth::Single(0.8);
     bordered_props.edges = vec![Edge::Top];
@@ -53,9 +54,11 @@ pub fn contact_section() -> Markup {
                 div class="page-content" {
                     div class="page-title" {}
                     p {
-                        ("For booking, press enquiries and collaborations please contact: ")
-                        a href="mailto:info@morningstarmusic.club"{
-                            ("info@morningstarmusic.club")
+                        (config.contact_text)
+                        @if !config.contact_email.trim().is_empty() {
+                            a href=(format!("mailto:{}", config.contact_email)) {
+                                (config.contact_email)
+                            }
                         }
                     }
                 }
@@ -1,18 +1,123 @@
+use canopie_themes_base::{
+    data::SiteData,
+    diagnostics::Diagnostics,
+    menu::{MenuLabels, MenuPages, is_active},
+    page::render_page_content,
+};
 use canopie_utils::{
     components::Formatter,
     header::Header,
+    models::{Menu, MenuItem},
     renderer::{PageResponse, PageResult},
 };
 use maud::html;
 
-use crate::templates::single::single_page;
+use crate::{
+    MorningStarConfig,
+    templates::{
+        single::{SinglePageConfig, single_page},
+        spa::{hero_menu_items, item_labels, top_level_items},
+    },
+};
+
+/// Slug of the optional CMS page whose content is shown on the 404 page.
+pub(crate) const NOT_FOUND_SLUG: &str = "404";
+
+/// Path the 404 page is looked up by, which [`SiteData::page`] expects with
+/// a leading slash.
+const NOT_FOUND_PATH: &str = "/404";
+
+pub fn morningstar_not_found<D: SiteData + ?Sized>(
+    data: &D,
+    config: &MorningStarConfig,
+    website_id: &str,
+    menu_input: &(Menu, Vec<MenuItem>),
+    formatter: Formatter,
+    headers: &mut Header,
+    diagnostics: &Diagnostics,
+) -> PageResponse {
+    let language = data.default_language(website_id);
+    let labels = MenuLabels::for_language(data.menu_item_labels(website_id), language.as_deref());
+    let menu_pages = MenuPages::load(data, std::slice::from_ref(menu_input));
+    let menu_items = top_level_items(&menu_input.1);
+    let item_labels = item_labels(&menu_items, &menu_pages, &labels);
+    let menu = hero_menu_items(config, &menu_items, &menu_pages, &item_labels);
+
+    let cms_content = data
+        .page(website_id, NOT_FOUND_PATH)
+        .filter(|(page, _page_content)| is_active(page.active))
+        .and_then(|(page, page_content)| page_content.map(|page_content| (page, page_content)))
+        .map(|(page, page_content)| {
+            render_page_content(page.id.as_str(), page_content, &formatter, diagnostics)
+        });
+
+    let content = html! {
+        div class="not-found" {
+            h1 class="not-found-title" { "Page not found" }
+            @if let Some(cms_content) = cms_content {
+                div class="not-found-content" { (cms_content) }
+            } @else {
+                p { "The page you were looking for doesn't exist or has moved." }
+            }
+            @if !menu.is_empty() {
+                nav class="not-found-menu" aria-label="Main" {
+                    ul {
+                        @for item in &menu {
+                            li { a href=(format!("/{}", item.slug)) { (item.label) } }
+                        }
+                    }
+                }
+            }
+            a class="not-found-home" href="/" { "Back to the homepage" }
+        }
+    };
 
-pub fn morningstar_not_found(formatter: Formatter, headers: &mut Header) -> PageResponse {
     PageResponse {
-        title: "404 Page not found".to_string(),
+        title: "Page not found".to_string(),
         result: PageResult::NotFound(single_page(
+            config,
+            &SinglePageConfig::default(),
             "/404",
-            html! { ("404 Page not found!!") },
+            content,
+            headers,
+        )),
+        formatter,
+    }
+}
+
+/// Answers every path of a website that has no Main menu, which the
+/// one-page layout is built from. Visitors see a neutral placeholder; what
+/// is missing is only reported as a diagnostic.
+pub fn morningstar_not_configured(
+    config: &MorningStarConfig,
+    website_id: &str,
+    formatter: Formatter,
+    headers: &mut Header,
+    diagnostics: &Diagnostics,
+) -> PageResponse {
+    diagnostics.warn(
+        None,
+        None,
+        format!(
+            "Website {} has no Main menu to build its pages from; add a menu named \"Main\" in the CMS",
+            website_id
+        ),
+    );
+
+    let content = html! {
+        div class="not-found" {
+            h1 class="not-found-title" { "Coming soon" }
+            p { "This site doesn't have any pages yet. Please check back later." }
+        }
+    };
+
+    PageResponse {
+        title: "Coming soon".to_string(),
+        result: PageResult::NotFound(single_page(
+            config,
+            &SinglePageConfig::default(),
+            "/not-configured",
+            content,
             headers,
         )),
         formatter,
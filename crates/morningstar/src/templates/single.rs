@@ -1,23 +1,56 @@
 use canopie_macros::ThemeConfig;
-use canopie_utils::header::Header;
+use canopie_themes_base::config::resolve_fields;
+use canopie_utils::{header::Header, models::Page};
 use maud::{Markup, html};
 use serde::{Deserialize, Serialize};
 
-use crate::components::bordered::{
-    BorderWidth, Counts, Edge, Props as BorderedProps, bordered_component,
+use crate::{
+    MorningStarConfig,
+    components::bordered::{BorderWidth, Counts, Edge, bordered_component},
+    options::PageStylesheets,
 };
 
-#[derive(Serialize, Deserialize, Debug, ThemeConfig)]
-pub struct SinglePageConfig {}
-
-pub fn single_page(path: &str, content: Markup, headers: &mut Header) -> Markup {
-    let mut bordered_props = BorderedProps::default();
-    bordered_props.seed = path.to_string();
-    // bordered_props.intensity = (0.1, 0.2);
-    bordered_props.intensity = (0.03, 0.06);
-    bordered_props.goo = 1.0;
-    bordered_props.id_card = "bigger".to_string();
-    bordered_props.color = "#e5aaa7".to_string();
+#[derive(Serialize, Deserialize, Debug, Default, ThemeConfig)]
+pub struct SinglePageConfig {
+    /// File stem of an extra stylesheet from `static/styles`.
+    #[theme(interface = "SelectDropdown", default = "", width = "Half")]
+    pub stylesheet: String,
+    #[theme(interface = "Input", default = "", width = "Half")]
+    pub body_class: String,
+}
+
+impl SinglePageConfig {
+    /// The page's options, with pages saved before `stylesheet` existed
+    /// keeping the stylesheet their slug used to give them.
+    pub fn for_page(page: &Page) -> Self {
+        let mut config: Self = resolve_fields(page.options.clone());
+
+        if let Some(stylesheet) = PageStylesheets::legacy_for_slug(&page.slug)
+            .filter(|_| config.stylesheet.trim().is_empty())
+        {
+            config.stylesheet = stylesheet.to_string();
+        }
+
+        config
+    }
+
+    /// The theme's body classes followed by the page's own.
+    fn body_class(&self) -> String {
+        match self.body_class.trim() {
+            "" => "text-gray-900 font-sans".to_string(),
+            extra => format!("text-gray-900 font-sans {}", extra),
+        }
+    }
+}
+
+pub fn single_page(
+    config: &MorningStarConfig,
+    page_config: &SinglePageConfig,
+    path: &str,
+    content: Markup,
+    headers: &mut Header,
+) -> Markup {
+    let mut bordered_props = config.bordered_props(path, (0.03, 0.06));
     bordered_props.counts = Some(Counts::XY(20, 30));
     bordered_props.border_width = BorderWidth::WidthHeight(0.06, 0.01);
     bordered_props.edges = vec![Edge::Top, Edge::Bottom, Edge::Left, Edge::Right];
@@ -39,6 +72,16 @@ pub fn single_page(path: &str, content: Markup, headers: &mut Header) -> Markup
         Some(bordered_props),
     );
 
+    let stylesheet = if page_config.stylesheet.trim().is_empty() {
+        None
+    } else {
+        let href = PageStylesheets::href(page_config.stylesheet.trim());
+        if href.is_none() {
+            tracing::warn!("Unknown page stylesheet: {}", page_config.stylesheet);
+        }
+        href
+    };
+
     headers.add_asset(
         html!{
             link rel="preconnect" href="https://fonts.googleapis.com" crossorigin="";
@@ -46,14 +89,14 @@ pub fn single_page(path: &str, content: Markup, headers: &mut Header) -> Markup
             link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=Cabin+Sketch:wght@400;700&display=swap";
             link rel="stylesheet" href="/_canopie/static/styles/index.css";
             link rel="stylesheet" href="/_canopie/static/styles/single-page.css";
-            @if path == "/liminal-zone" {
-                link rel="stylesheet" href="/_canopie/static/styles/liminal-zone.css";
+            @if let Some(href) = stylesheet {
+                link rel="stylesheet" href=(href);
             }
         },
     );
 
     html! {
-        body class="text-gray-900 font-sans" {
+        body class=(page_config.body_class()) style=(config.style_vars()) {
             div class="layout-epk"{
                 div class="background-image"{}
                 div class="header"{
@@ -78,3 +121,47 @@ pub fn single_page(path: &str, content: Markup, headers: &mut Header) -> Markup
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use canopie_themes_base::data::test_support::{self, row};
+    use serde_json::json;
+
+    use super::*;
+
+    fn page(slug: &str, options: serde_json::Value) -> Page {
+        let mut page = test_support::page("page-single", slug, 1);
+        page["options"] = options;
+        row(page)
+    }
+
+    #[test]
+    fn the_liminal_zone_keeps_its_stylesheet_until_one_is_picked() {
+        assert_eq!(
+            SinglePageConfig::for_page(&page("liminal-zone", json!({}))).stylesheet,
+            "liminal-zone"
+        );
+        assert_eq!(
+            SinglePageConfig::for_page(&page("liminal-zone", json!({ "stylesheet": "" })))
+                .stylesheet,
+            "liminal-zone"
+        );
+        assert_eq!(
+            SinglePageConfig::for_page(&page("about", json!({}))).stylesheet,
+            ""
+        );
+    }
+
+    #[test]
+    fn page_body_classes_follow_the_theme_ones() {
+        let config = SinglePageConfig {
+            body_class: "  dark  ".to_string(),
+            ..SinglePageConfig::default()
+        };
+        assert_eq!(config.body_class(), "text-gray-900 font-sans dark");
+        assert_eq!(
+            SinglePageConfig::default().body_class(),
+            "text-gray-900 font-sans"
+        );
+    }
+}
@@ -1,47 +1,92 @@
+use std::collections::HashMap;
+
 use canopie_macros::ThemeConfig;
+use canopie_themes_base::{
+    config::resolve_fields,
+    content::parse_page_content,
+    data::SiteData,
+    diagnostics::Diagnostics,
+    menu::{MenuLabels, MenuPages, is_active},
+};
 use canopie_utils::{
     components::{Asset, AssetOptions, Formatter, build_components},
-    db::PgPool,
     header::Header,
-    models::{Menu, MenuItem},
+    models::{Menu, MenuItem, Page},
     renderer::PageResponse,
-    theme_utils::{get_page, get_page_from_id},
-    types::tiptap::{empty_tiptap_node, to_tiptap_node},
 };
 use maud::{Markup, html};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    sections::render_section,
-    templates::{home, home_section, not_found::morningstar_not_found, single::single_page},
+    MorningStarConfig, SectionStyle,
+    sections::{HeroMenuItem, render_section},
+    templates::{
+        home, home_section,
+        not_found::{NOT_FOUND_SLUG, morningstar_not_found},
+        single::{SinglePageConfig, single_page},
+    },
 };
 
-#[derive(Serialize, Deserialize, Debug, ThemeConfig)]
-pub struct SpaPageConfig {}
+#[derive(Serialize, Deserialize, Debug, Default, ThemeConfig)]
+pub struct SpaPageConfig {
+    #[theme(interface = "SelectDropdown", default = "default", width = "Half")]
+    pub section_style: SectionStyle,
+    /// Replaces the stylesheet's background for the section.
+    #[theme(interface = "FileImage")]
+    pub section_background: Option<String>,
+    /// Shown above the content in place of a text heading.
+    #[theme(interface = "FileImage")]
+    pub title_image: Option<String>,
+    /// Replaces the page's link in the hero menu with this image.
+    #[theme(interface = "FileImage")]
+    pub menu_icon: Option<String>,
+}
+
+impl SpaPageConfig {
+    /// The page's options, with pages saved before `section_style` existed
+    /// keeping the style their slug used to give them.
+    pub fn for_page(page: &Page) -> Self {
+        let mut config: Self = resolve_fields(page.options.clone());
+
+        if page.options.get("section_style").is_none() {
+            config.section_style = SectionStyle::legacy_for_slug(&page.slug);
+        }
+
+        config
+    }
+}
 
-pub fn build_content_for_menu_pages(
-    pool: &PgPool,
+pub fn build_content_for_menu_pages<D: SiteData + ?Sized>(
+    data: &D,
+    config: &MorningStarConfig,
     path: &str,
     website_id: &String,
     menu_input: &(Menu, Vec<MenuItem>),
     formatter: Formatter,
     headers: &mut Header,
+    diagnostics: &Diagnostics,
 ) -> PageResponse {
     let (_menu, menu_items) = menu_input;
+    let menu_items = top_level_items(menu_items);
 
-    let page_result = get_page(pool, website_id, path);
-    let page_content = page_result
-        .clone()
-        .map(|result| result.page_content)
-        .unwrap_or(None);
-
-    if page_result.is_none() || page_content.is_none() {
-        return morningstar_not_found(formatter, headers);
-    }
-
-    let page = page_result.unwrap().page;
+    // The 404 page's content only appears on the not-found page, never at
+    // its own path.
+    let Some((page, Some(page_content))) = data
+        .page(website_id, path)
+        .filter(|(page, _page_content)| is_active(page.active))
+        .filter(|(page, _page_content)| page.slug.trim_matches('/') != NOT_FOUND_SLUG)
+    else {
+        return morningstar_not_found(
+            data,
+            config,
+            website_id,
+            menu_input,
+            formatter,
+            headers,
+            diagnostics,
+        );
+    };
 
-    let page_content = page_content.unwrap();
     let given_page_id = page.id.to_string();
 
     let page_in_menu = menu_items
@@ -49,25 +94,28 @@ pub fn build_content_for_menu_pages(
         .any(|item| item.page_id.clone().unwrap_or("NO ID".to_string()) == given_page_id);
 
     if !page_in_menu {
-        let content = match to_tiptap_node(page_content.content) {
-            Ok(content) => content,
-            Err(error) => {
-                // tracing::error!("Error parsing content: {}", error);
-                // tracing::error!(
-                //     "Value failed to parse: {:?}",
-                //     serde_json::to_string(&page_content.content)
-                // );
-                empty_tiptap_node(Some("Error parsing content"))
-            }
-        };
+        let content = parse_page_content(given_page_id.as_str(), page_content, diagnostics);
         let (components, formatter) = build_components(content, None, Some(formatter.clone()));
         return PageResponse::new(
             page.title,
-            single_page(path, components, headers),
+            single_page(
+                config,
+                &SinglePageConfig::for_page(&page),
+                path,
+                components,
+                headers,
+            ),
             formatter,
         );
     }
 
+    let menu_pages = MenuPages::load(data, std::slice::from_ref(menu_input));
+    let labels = MenuLabels::for_language(
+        data.menu_item_labels(website_id),
+        Some(page_content.language.as_str()),
+    );
+    let item_labels = item_labels(&menu_items, &menu_pages, &labels);
+    let hero_menu = hero_menu_items(config, &menu_items, &menu_pages, &item_labels);
     let mut component_list: Vec<Markup> = vec![];
 
     for item in menu_items {
@@ -77,18 +125,27 @@ pub fn build_content_for_menu_pages(
         let item_details = if item_id.unwrap_or("NO ID".to_string()) == given_page_id {
             Some((page.clone(), page_content.clone()))
         } else if let Some(page_id) = item.page_id.clone() {
-            let page_fetch = get_page_from_id(pool, &page_id);
-            let (item_page, item_page_content) = if page_fetch.is_none() {
-                println!("Content not found for page ID: {}", page_id);
-                (page.clone(), None)
-            } else {
-                page_fetch.unwrap()
+            let Some((item_page, item_page_content)) = menu_pages.get(&page_id).cloned() else {
+                diagnostics.warn(
+                    Some(page_id.as_str()),
+                    None,
+                    "Menu item links to a missing page",
+                );
+                continue;
             };
 
+            if !is_active(item_page.active) {
+                continue;
+            }
+
             match item_page_content {
                 Some(item_page_content) => Some((item_page, item_page_content)),
                 None => {
-                    println!("Content not found for page: /{}", item_page.slug);
+                    diagnostics.warn(
+                        Some(item_page.id.as_str()),
+                        None,
+                        format!("No content found for page /{}", item_page.slug),
+                    );
                     None
                 }
             }
@@ -98,25 +155,25 @@ pub fn build_content_for_menu_pages(
 
         let (components, new_formatter) = match item_details {
             Some((item_page, item_page_content)) => {
-                let content = match to_tiptap_node(item_page_content.content) {
-                    Ok(content) => content,
-                    Err(error) => {
-                        // tracing::error!("Error parsing content: {}", error);
-                        // tracing::error!(
-                        //     "Value failed to parse: {:?}",
-                        //     serde_json::to_string(&item_page_content.content)
-                        // );
-                        empty_tiptap_node(Some("Error parsing content"))
-                    }
-                };
+                let content =
+                    parse_page_content(item_page.id.as_str(), item_page_content, diagnostics);
 
                 let (components, formatter) =
                     build_components(content, None, Some(formatter.clone()));
                 let path = item_page.slug.as_str();
+                let section_config = SpaPageConfig::for_page(&item_page);
 
-                let title = item_page.title.clone();
+                let title = item_labels
+                    .get(&item.id)
+                    .cloned()
+                    .unwrap_or_else(|| item_page.title.clone());
                 (
-                    home_section(path, title, render_section(path, components)),
+                    home_section(
+                        path,
+                        title,
+                        section_config.section_style,
+                        render_section(&section_config, components),
+                    ),
                     formatter,
                 )
             }
@@ -135,6 +192,8 @@ pub fn build_content_for_menu_pages(
     PageResponse::new(
         page.title,
         home(
+            config,
+            &hero_menu,
             html! {
                 @for component in component_list {
                     (component)
@@ -145,3 +204,112 @@ pub fn build_content_for_menu_pages(
         formatter,
     )
 }
+
+/// Sections and the hero menu follow the menu's order, top level only.
+pub(crate) fn top_level_items(menu_items: &[MenuItem]) -> Vec<&MenuItem> {
+    let mut items: Vec<&MenuItem> = menu_items
+        .iter()
+        .filter(|item| item.parent_id.is_none())
+        .collect();
+    items.sort_by_key(|item| item.order);
+    items
+}
+
+/// The label of each item linking to an active page, by item id: the label
+/// written for the page's language, or else the page title. The hero menu
+/// and the section's `page-title` both show it.
+pub(crate) fn item_labels(
+    menu_items: &[&MenuItem],
+    menu_pages: &MenuPages,
+    labels: &MenuLabels,
+) -> HashMap<String, String> {
+    menu_items
+        .iter()
+        .filter_map(|item| {
+            let (page, _page_content) = menu_pages.active(item.page_id.as_deref()?)?;
+            let label = labels
+                .get(&item.id)
+                .map(str::to_string)
+                .unwrap_or_else(|| page.title.clone());
+
+            Some((item.id.clone(), label))
+        })
+        .collect()
+}
+
+pub(crate) fn hero_menu_items(
+    config: &MorningStarConfig,
+    menu_items: &[&MenuItem],
+    menu_pages: &MenuPages,
+    item_labels: &HashMap<String, String>,
+) -> Vec<HeroMenuItem> {
+    let mut hero_menu: Vec<HeroMenuItem> = menu_items
+        .iter()
+        .filter_map(|item| {
+            let (page, _page_content) = menu_pages.active(item.page_id.as_deref()?)?;
+
+            Some(HeroMenuItem {
+                label: item_labels
+                    .get(&item.id)
+                    .cloned()
+                    .unwrap_or_else(|| page.title.clone()),
+                slug: page.slug.trim_matches('/').to_string(),
+                icon: SpaPageConfig::for_page(page).menu_icon,
+            })
+        })
+        .collect();
+
+    // The contact section is part of the theme, not a page in the menu.
+    if config.show_contact_in_menu && !hero_menu.iter().any(|item| item.slug == "contact") {
+        hero_menu.push(HeroMenuItem {
+            label: config.contact_label.clone(),
+            slug: "contact".to_string(),
+            icon: None,
+        });
+    }
+
+    hero_menu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contact_is_added_to_the_hero_menu_by_default() {
+        let menu = hero_menu_items(
+            &MorningStarConfig::default(),
+            &[],
+            &MenuPages::default(),
+            &HashMap::new(),
+        );
+
+        let slugs: Vec<&str> = menu.iter().map(|item| item.slug.as_str()).collect();
+        assert_eq!(slugs, ["contact"]);
+        assert_eq!(menu[0].label, "Contact");
+    }
+
+    #[test]
+    fn the_contact_label_comes_from_the_config() {
+        let config = MorningStarConfig {
+            contact_label: "Booking".to_string(),
+            ..MorningStarConfig::default()
+        };
+
+        let menu = hero_menu_items(&config, &[], &MenuPages::default(), &HashMap::new());
+
+        assert_eq!(menu[0].label, "Booking");
+    }
+
+    #[test]
+    fn contact_can_be_left_out_of_the_hero_menu() {
+        let config = MorningStarConfig {
+            show_contact_in_menu: false,
+            ..MorningStarConfig::default()
+        };
+
+        let menu = hero_menu_items(&config, &[], &MenuPages::default(), &HashMap::new());
+
+        assert!(menu.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 function updateHeaderRotation() {
     // const header = document.querySelector<HTMLElement>("section#band::after");
-    var bandSection = document.querySelector("section#band");
+    var bandSection = document.querySelector("section.section-style-band");
     if (!bandSection) return;
     var header = document.querySelector(".band-header");
     if (!header) return;
@@ -0,0 +1,183 @@
+use canopie_utils::{
+    models::{Project, ProjectExtra},
+    themes::{ThemeReference, ThemeRestriction},
+};
+
+use crate::registry::{ThemeEntry, registry};
+
+/// Whether a project may use a theme with the given restriction.
+///
+/// A restricted theme lists the keys that unlock it. A project is entitled
+/// when its own id is listed, or when it has an active project extra whose
+/// `extra` value is listed.
+pub fn is_entitled(
+    restriction: &ThemeRestriction,
+    project: &Project,
+    extras: &[ProjectExtra],
+) -> bool {
+    match restriction {
+        ThemeRestriction::None => true,
+        ThemeRestriction::Restricted(allowed) => {
+            allowed.iter().any(|key| *key == project.id)
+                || extras.iter().any(|extra| {
+                    extra.project_id == project.id
+                        && extra.active != 0
+                        && allowed.iter().any(|key| *key == extra.extra)
+                })
+        }
+    }
+}
+
+pub(crate) fn entry_is_available(
+    entry: &ThemeEntry,
+    project: &Project,
+    extras: &[ProjectExtra],
+) -> bool {
+    entry
+        .overview
+        .map(|get_theme_overview| is_entitled(&get_theme_overview().restricted, project, extras))
+        .unwrap_or(false)
+}
+
+/// Whether a theme is offered in theme listings. The fallback theme only
+/// renders websites whose theme is unknown, so it is never offered.
+pub(crate) fn is_listed(entry: &ThemeEntry) -> bool {
+    registry()
+        .fallback()
+        .is_none_or(|fallback| fallback.id != entry.id)
+}
+
+/// Themes the given project is allowed to pick.
+pub fn get_themes_for_project(project: &Project, extras: &[ProjectExtra]) -> Vec<ThemeReference> {
+    registry()
+        .entries()
+        .iter()
+        .filter(|entry| is_listed(entry))
+        .filter(|entry| entry_is_available(entry, project, extras))
+        .filter_map(|entry| entry.reference.map(|get_reference| get_reference()))
+        .collect()
+}
+
+/// Whether the given project is allowed to use `theme_id`.
+///
+/// Unknown theme ids are never available.
+pub fn is_theme_available(theme_id: &str, project: &Project, extras: &[ProjectExtra]) -> bool {
+    registry()
+        .get(theme_id)
+        .map(|entry| entry_is_available(entry, project, extras))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use canopie_themes_base::data::test_support::{self, project_extra, row};
+
+    use super::*;
+
+    fn project(id: &str) -> Project {
+        row(test_support::project(id))
+    }
+
+    fn extra(project_id: &str, extra: &str, active: i32) -> ProjectExtra {
+        row(project_extra(project_id, extra, active))
+    }
+
+    fn theme_ids(project: &Project, extras: &[ProjectExtra]) -> Vec<String> {
+        get_themes_for_project(project, extras)
+            .into_iter()
+            .map(|reference| reference.id)
+            .collect()
+    }
+
+    #[test]
+    fn unrestricted_themes_are_available_to_every_project() {
+        let project = project("some-project");
+
+        assert!(is_entitled(&ThemeRestriction::None, &project, &[]));
+        assert!(is_theme_available("atlas", &project, &[]));
+        assert!(is_theme_available("base", &project, &[]));
+        assert!(theme_ids(&project, &[]).contains(&"atlas".to_string()));
+    }
+
+    #[test]
+    fn the_fallback_theme_is_not_listed() {
+        let project = project("morningstar");
+
+        assert!(!theme_ids(&project, &[]).contains(&"base".to_string()));
+        assert!(
+            !crate::get_themes()
+                .into_iter()
+                .any(|reference| reference.id == "base")
+        );
+        // Websites already using it still render with it.
+        assert!(is_theme_available("base", &project, &[]));
+    }
+
+    #[test]
+    fn restricted_theme_is_available_to_its_own_project() {
+        let project = project("morningstar");
+
+        assert!(is_theme_available("morningstar", &project, &[]));
+        assert!(theme_ids(&project, &[]).contains(&"morningstar".to_string()));
+    }
+
+    #[test]
+    fn restricted_theme_is_hidden_from_other_projects() {
+        let project = project("another-project");
+
+        assert!(!is_theme_available("morningstar", &project, &[]));
+        assert!(!theme_ids(&project, &[]).contains(&"morningstar".to_string()));
+    }
+
+    #[test]
+    fn active_project_extras_unlock_restricted_themes() {
+        let project = project("another-project");
+        let restriction = ThemeRestriction::Restricted(vec!["premium".to_string()]);
+
+        assert!(is_entitled(
+            &restriction,
+            &project,
+            &[extra("another-project", "premium", 1)]
+        ));
+        assert!(!is_entitled(
+            &restriction,
+            &project,
+            &[extra("another-project", "premium", 0)]
+        ));
+        assert!(!is_entitled(
+            &restriction,
+            &project,
+            &[extra("someone-else", "premium", 1)]
+        ));
+    }
+
+    #[test]
+    fn unknown_themes_are_never_available() {
+        assert!(!is_theme_available(
+            "no-such-theme",
+            &project("morningstar"),
+            &[]
+        ));
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn themes_a_project_is_not_entitled_to_render_with_the_fallback() {
+        use crate::{ThemeFallback, get_theme_for_project};
+
+        let selection =
+            get_theme_for_project("morningstar".to_string(), &project("another-project"), &[]);
+
+        assert_eq!(selection.renderer.name, "base");
+        assert_eq!(
+            selection.fallback,
+            Some(ThemeFallback::NotEntitled("morningstar".to_string()))
+        );
+
+        let selection =
+            get_theme_for_project("morningstar".to_string(), &project("morningstar"), &[]);
+
+        assert_eq!(selection.renderer.name, "morningstar");
+        assert!(!selection.used_fallback());
+    }
+}
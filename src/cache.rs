@@ -0,0 +1,216 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use canopie_themes_base::data::SiteData;
+#[cfg(feature = "embed")]
+use canopie_utils::{db::PgPool, renderer::ThemeRenderer};
+use canopie_utils::{header::Header, models::Website, renderer::PageResult};
+use maud::PreEscaped;
+
+/// An opt-in cache of rendered pages.
+///
+/// Entries are keyed on the theme, website id, path, a hash of the theme
+/// config and the [`ContentVersion`](canopie_themes_base::data::ContentVersion)
+/// of the website's pages, content and menus, so edits and deletions made
+/// through the CMS change the key and stale pages are never served. When the
+/// content version cannot be read, pages are rendered without touching the
+/// cache. Entries are evicted least recently used first once the cached HTML
+/// exceeds `max_bytes`.
+///
+/// The cache restores the [`Header`] a page was rendered with, so hosts
+/// should pass a fresh header for every request.
+pub struct RenderCache {
+    max_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CachedPage>,
+    bytes: usize,
+    tick: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    theme: String,
+    website_id: String,
+    path: String,
+    config_hash: u64,
+    updated_at: Option<i64>,
+    rows: i64,
+}
+
+struct CachedPage {
+    found: bool,
+    body: String,
+    headers: Header,
+    last_used: u64,
+}
+
+impl RenderCache {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Returns the cached page for `path`, or renders it with `render` and
+    /// caches the result. The key does not cover the render mode, so only
+    /// pass live renders.
+    pub fn render(
+        &self,
+        theme: &str,
+        data: &dyn SiteData,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+        render: impl FnOnce(&mut Header, Website) -> PageResult,
+    ) -> PageResult {
+        let Some(key) = CacheKey::new(theme, data, &website, path) else {
+            return render(headers, website);
+        };
+
+        if let Some(result) = self.lookup(&key, headers) {
+            return result;
+        }
+
+        let result = render(headers, website);
+        let (found, body) = match &result {
+            PageResult::Found(markup) => (true, markup.0.clone()),
+            PageResult::NotFound(markup) => (false, markup.0.clone()),
+        };
+        self.store(key, found, body, headers.clone());
+
+        result
+    }
+
+    /// [`RenderCache::render`] for a theme's [`ThemeRenderer`].
+    #[cfg(feature = "embed")]
+    pub fn build_content(
+        &self,
+        renderer: &ThemeRenderer,
+        pool: &PgPool,
+        headers: &mut Header,
+        website: Website,
+        path: &str,
+    ) -> PageResult {
+        self.render(
+            renderer.name.as_str(),
+            pool,
+            headers,
+            website,
+            path,
+            |headers, website| (renderer.build_content)(pool, headers, website, path),
+        )
+    }
+
+    /// Drops every cached page of a website. Call after saving anything the
+    /// cache key does not cover, such as uploaded files.
+    pub fn invalidate_website(&self, website_id: &str) {
+        let mut state = self.state();
+        state.entries.retain(|key, _| key.website_id != website_id);
+        state.bytes = state.entries.values().map(|page| page.body.len()).sum();
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state();
+        state.entries.clear();
+        state.bytes = 0;
+    }
+
+    /// The size of the cached HTML in bytes.
+    pub fn size(&self) -> usize {
+        self.state().bytes
+    }
+
+    fn lookup(&self, key: &CacheKey, headers: &mut Header) -> Option<PageResult> {
+        let mut state = self.state();
+        state.tick += 1;
+        let tick = state.tick;
+
+        let page = state.entries.get_mut(key)?;
+        page.last_used = tick;
+        *headers = page.headers.clone();
+
+        let markup = PreEscaped(page.body.clone());
+        Some(if page.found {
+            PageResult::Found(markup)
+        } else {
+            PageResult::NotFound(markup)
+        })
+    }
+
+    fn store(&self, key: CacheKey, found: bool, body: String, headers: Header) {
+        if body.len() > self.max_bytes {
+            return;
+        }
+
+        let mut state = self.state();
+        state.tick += 1;
+        let page = CachedPage {
+            found,
+            last_used: state.tick,
+            body,
+            headers,
+        };
+
+        state.bytes += page.body.len();
+        if let Some(replaced) = state.entries.insert(key, page) {
+            state.bytes -= replaced.body.len();
+        }
+
+        while state.bytes > self.max_bytes {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, page)| page.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = state.entries.remove(&oldest) {
+                state.bytes -= evicted.body.len();
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CacheKey {
+    /// `None` when the website's content version cannot be read, as a key
+    /// without it could serve stale pages.
+    fn new(theme: &str, data: &dyn SiteData, website: &Website, path: &str) -> Option<Self> {
+        let version = data.content_version(&website.id)?;
+
+        let mut hasher = DefaultHasher::new();
+        website
+            .theme_config
+            .as_ref()
+            .map(|config| config.to_string())
+            .hash(&mut hasher);
+
+        let updated_at = version
+            .last_updated
+            .into_iter()
+            .chain([website.updated_at])
+            .max()
+            .map(|updated_at| updated_at.and_utc().timestamp_micros());
+
+        Some(Self {
+            theme: theme.to_string(),
+            website_id: website.id.clone(),
+            path: path.to_string(),
+            config_hash: hasher.finish(),
+            updated_at,
+            rows: version.rows,
+        })
+    }
+}
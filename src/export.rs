@@ -0,0 +1,432 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use canopie_themes_base::{
+    content::RenderMode, data::SiteData, menu::MenuPages, page::RenderOutcome,
+};
+use canopie_utils::header::Header;
+use maud::{DOCTYPE, Markup, html};
+
+use crate::registry::registry;
+
+/// What a static export wrote to disk.
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    /// Routes written as `index.html` files.
+    pub pages: Vec<String>,
+    /// Routes the theme answered with its not-found page, which are skipped.
+    pub skipped: Vec<String>,
+    /// Uploaded `/_f/` files copied from the files directory.
+    pub files: Vec<String>,
+}
+
+/// Renders every page of a website with its theme and writes a static mirror
+/// to `out_dir`.
+///
+/// Each route becomes `<route>/index.html`, the theme's embedded `static/`
+/// directory is copied to `_canopie/static/`, and root-relative URLs in the
+/// pages and the copied stylesheets are rewritten to relative ones so the
+/// export works from any prefix. Uploaded files the pages reference under
+/// `/_f/` are copied from `files_dir` when one is given.
+///
+/// The export only contains what it can render or copy: assets served by the
+/// Canopie host itself are not included. Once everything is written, any
+/// root-relative link that does not resolve to a file in `out_dir` is
+/// reported as an error.
+pub fn export_site(
+    data: &dyn SiteData,
+    website_id: &str,
+    out_dir: &Path,
+    files_dir: Option<&Path>,
+) -> Result<ExportSummary, String> {
+    let website = data
+        .website(website_id)
+        .ok_or_else(|| format!("Unknown website: {}", website_id))?;
+
+    let entry = registry()
+        .get(website.theme.as_str())
+        .or_else(|| registry().fallback())
+        .ok_or_else(|| format!("No theme available for {}", website.theme))?;
+    let render_site = entry
+        .render_site
+        .ok_or_else(|| format!("Theme {} cannot render from site data", entry.id))?;
+
+    let mut summary = ExportSummary::default();
+    let mut links = Vec::new();
+
+    for route in site_routes(data, website_id) {
+        let mut headers = Header::default();
+
+        let body = match render_site(
+            data,
+            &mut headers,
+            website.clone(),
+            route.as_str(),
+            RenderMode::Live,
+        ) {
+            RenderOutcome::Found(body) => body,
+            RenderOutcome::NotFound(_) => {
+                summary.skipped.push(route);
+                continue;
+            }
+            RenderOutcome::NotConfigured(_) => {
+                return Err(format!(
+                    "Website {} is not set up for the {} theme",
+                    website_id, entry.id
+                ));
+            }
+        };
+
+        let file = route_file(out_dir, route.as_str());
+        let depth = route
+            .trim_matches('/')
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .count();
+        let html = document(&headers, body);
+        links.extend(root_links(html.as_str()));
+        let html = relative_urls(html.as_str(), depth);
+
+        write_file(&file, html.as_bytes())?;
+        summary.pages.push(route);
+    }
+
+    if let Some(static_dir) = entry.static_dir {
+        let target = out_dir.join("_canopie").join("static");
+        fs::create_dir_all(&target)
+            .map_err(|error| format!("Could not create {}: {}", target.display(), error))?;
+        static_dir()
+            .extract(&target)
+            .map_err(|error| format!("Could not write {}: {}", target.display(), error))?;
+        links.extend(relative_stylesheets(out_dir, &target)?);
+    }
+
+    links.sort();
+    links.dedup();
+
+    if let Some(files_dir) = files_dir {
+        for link in &links {
+            let Some(name) = link_path(link).strip_prefix("/_f/") else {
+                continue;
+            };
+            let source = files_dir.join(name);
+            if !source.is_file() {
+                continue;
+            }
+            let contents = fs::read(&source)
+                .map_err(|error| format!("Could not read {}: {}", source.display(), error))?;
+            write_file(&out_dir.join("_f").join(name), &contents)?;
+            summary.files.push(link_path(link).to_string());
+        }
+    }
+
+    let unresolved: Vec<&str> = links
+        .iter()
+        .map(String::as_str)
+        .filter(|link| !resolves(out_dir, link))
+        .collect();
+    if !unresolved.is_empty() {
+        return Err(format!(
+            "Exported {} to {} with {} unresolved link(s):\n  {}",
+            website_id,
+            out_dir.display(),
+            unresolved.len(),
+            unresolved.join("\n  ")
+        ));
+    }
+
+    Ok(summary)
+}
+
+/// Routes for every page of the website and every page its menus link to.
+fn site_routes(data: &dyn SiteData, website_id: &str) -> Vec<String> {
+    let menus = data.menus(website_id);
+    let menu_pages = MenuPages::load(data, &menus);
+    let linked_pages = menus
+        .iter()
+        .flat_map(|(_menu, items)| items)
+        .filter_map(|item| item.page_id.as_deref())
+        .filter_map(|page_id| menu_pages.get(page_id))
+        .map(|(page, _content)| page.clone());
+
+    let mut routes: Vec<String> = data
+        .pages(website_id)
+        .into_iter()
+        .chain(linked_pages)
+        .map(|page| {
+            if page.home || page.slug.is_empty() {
+                "/".to_string()
+            } else {
+                format!("/{}", page.slug.trim_matches('/'))
+            }
+        })
+        .collect();
+
+    routes.sort();
+    routes.dedup();
+    routes
+}
+
+fn route_file(out_dir: &Path, route: &str) -> PathBuf {
+    route
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .fold(out_dir.to_path_buf(), |path, part| path.join(part))
+        .join("index.html")
+}
+
+fn document(headers: &Header, body: Markup) -> String {
+    html! {
+        (DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                (headers.render())
+            }
+            (body)
+        }
+    }
+    .into_string()
+}
+
+/// Attribute and CSS openings whose root-relative URLs the export rewrites.
+const URL_OPENINGS: [&str; 5] = ["href=\"", "src=\"", "url('", "url(\"", "url("];
+
+/// Rewrites the root-relative URLs of every stylesheet under `dir` relative
+/// to the stylesheet's own directory, and returns the URLs it found.
+fn relative_stylesheets(out_dir: &Path, dir: &Path) -> Result<Vec<String>, String> {
+    let mut links = Vec::new();
+    let entries = fs::read_dir(dir)
+        .map_err(|error| format!("Could not read {}: {}", dir.display(), error))?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|error| format!("Could not read {}: {}", dir.display(), error))?
+            .path();
+
+        if path.is_dir() {
+            links.extend(relative_stylesheets(out_dir, &path)?);
+            continue;
+        }
+        if path.extension().is_none_or(|extension| extension != "css") {
+            continue;
+        }
+
+        let css = fs::read_to_string(&path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+        let depth = path
+            .parent()
+            .and_then(|parent| parent.strip_prefix(out_dir).ok())
+            .map_or(0, |parent| parent.components().count());
+        links.extend(root_links(css.as_str()));
+        write_file(&path, relative_urls(css.as_str(), depth).as_bytes())?;
+    }
+
+    Ok(links)
+}
+
+/// Root-relative URLs referenced from `text`, protocol-relative ones excluded.
+fn root_links(text: &str) -> Vec<String> {
+    URL_OPENINGS
+        .iter()
+        .flat_map(|opening| {
+            let root = format!("{}/", opening);
+            text.match_indices(root.as_str())
+                .map(|(index, _)| &text[index + opening.len()..])
+                .filter(|rest| !rest.starts_with("//"))
+                .map(|rest| {
+                    let end = rest
+                        .find(|c: char| matches!(c, '"' | '\'' | ')') || c.is_whitespace())
+                        .unwrap_or(rest.len());
+                    rest[..end].to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// `link` without its query string or fragment.
+fn link_path(link: &str) -> &str {
+    link.split(['?', '#']).next().unwrap_or(link)
+}
+
+/// Whether a root-relative link points at a file, or a route's `index.html`,
+/// in the export.
+fn resolves(out_dir: &Path, link: &str) -> bool {
+    let target = out_dir.join(link_path(link).trim_start_matches('/'));
+    target.is_file() || target.join("index.html").is_file()
+}
+
+/// Rewrites root-relative `href`, `src` and CSS `url()` references so they
+/// resolve from a file `depth` directories below the export root.
+fn relative_urls(html: &str, depth: usize) -> String {
+    let prefix = if depth == 0 {
+        "./".to_string()
+    } else {
+        "../".repeat(depth)
+    };
+
+    URL_OPENINGS
+        .iter()
+        .fold(html.to_string(), |html, attribute| {
+            let root = format!("{}/", attribute);
+            let mut rewritten = String::with_capacity(html.len());
+            let mut rest = html.as_str();
+
+            while let Some(index) = rest.find(root.as_str()) {
+                let after = &rest[index + root.len()..];
+                rewritten.push_str(&rest[..index]);
+                rewritten.push_str(attribute);
+                if after.starts_with('/') {
+                    // Protocol-relative URL, leave it alone.
+                    rewritten.push('/');
+                } else {
+                    rewritten.push_str(prefix.as_str());
+                }
+                rest = after;
+            }
+
+            rewritten.push_str(rest);
+            rewritten
+        })
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Could not create {}: {}", parent.display(), error))?;
+    }
+
+    fs::write(path, contents)
+        .map_err(|error| format!("Could not write {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use canopie_themes_base::data::test_support::{WEBSITE_ID, fixture};
+
+    use super::*;
+
+    /// An empty directory for one test to export into.
+    fn out_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("canopie-export-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn routes_become_index_files() {
+        let out = Path::new("/tmp/out");
+
+        assert_eq!(route_file(out, "/"), out.join("index.html"));
+        assert_eq!(route_file(out, "/music"), out.join("music/index.html"));
+        assert_eq!(
+            route_file(out, "/about/team/"),
+            out.join("about/team/index.html")
+        );
+    }
+
+    #[test]
+    fn root_relative_urls_point_back_to_the_export_root() {
+        let html = r#"<link href="/_canopie/static/base/styles/base.css"><img src="/_f/cover.png"><div style="background-image: url('/_f/band.jpg')"></div>"#;
+
+        assert_eq!(
+            relative_urls(html, 0),
+            r#"<link href="./_canopie/static/base/styles/base.css"><img src="./_f/cover.png"><div style="background-image: url('./_f/band.jpg')"></div>"#
+        );
+        assert_eq!(
+            relative_urls(html, 2),
+            r#"<link href="../../_canopie/static/base/styles/base.css"><img src="../../_f/cover.png"><div style="background-image: url('../../_f/band.jpg')"></div>"#
+        );
+    }
+
+    #[test]
+    fn absolute_and_protocol_relative_urls_are_left_alone() {
+        let html =
+            r#"<script src="//cdn.example.com/app.js"></script><a href="https://bandcamp.com/">"#;
+
+        assert_eq!(relative_urls(html, 1), html);
+    }
+
+    #[test]
+    fn root_relative_links_are_collected_without_protocol_relative_ones() {
+        let html = r#"<a href="/about">About</a><script src="//cdn.example.com/app.js"></script><div style="background-image: url('/_f/band.jpg?v=2')"></div>"#;
+
+        let mut links = root_links(html);
+        links.sort();
+
+        assert_eq!(links, vec!["/_f/band.jpg?v=2", "/about"]);
+    }
+
+    #[test]
+    fn stylesheets_are_rewritten_relative_to_their_own_directory() {
+        let out = out_dir("stylesheets");
+        let static_dir = out.join("_canopie/static");
+        write_file(
+            &static_dir.join("styles/home.css"),
+            br#".hero { background-image: url("/_canopie/static/images/banner.webp"); }"#,
+        )
+        .unwrap();
+
+        let links = relative_stylesheets(&out, &static_dir).unwrap();
+
+        assert_eq!(links, vec!["/_canopie/static/images/banner.webp"]);
+        assert_eq!(
+            fs::read_to_string(static_dir.join("styles/home.css")).unwrap(),
+            r#".hero { background-image: url("../../../_canopie/static/images/banner.webp"); }"#
+        );
+
+        fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn links_resolve_to_exported_files_and_routes() {
+        let out = out_dir("resolves");
+        write_file(&out.join("about/index.html"), b"").unwrap();
+        write_file(&out.join("_f/cover.png"), b"").unwrap();
+
+        assert!(resolves(&out, "/about"));
+        assert!(resolves(&out, "/about/#team"));
+        assert!(resolves(&out, "/_f/cover.png?v=2"));
+        assert!(!resolves(&out, "/_f/missing.png"));
+        assert!(!resolves(&out, "/music"));
+
+        fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn export_writes_nested_routes_and_skips_missing_pages() {
+        let out = out_dir("fixture");
+
+        let summary = export_site(&fixture("site.json"), WEBSITE_ID, &out, None).unwrap();
+
+        assert!(summary.pages.contains(&"/".to_string()));
+        assert!(summary.pages.contains(&"/about".to_string()));
+        assert!(summary.pages.contains(&"/about/team".to_string()));
+        assert_eq!(summary.skipped, vec!["/draft".to_string()]);
+
+        assert!(out.join("index.html").is_file());
+        assert!(out.join("about/index.html").is_file());
+        assert!(!out.join("draft").exists());
+
+        let nested = fs::read_to_string(out.join("about/team/index.html")).unwrap();
+        assert!(nested.contains("A nested page two levels deep."));
+        assert!(!nested.contains("href=\"/_canopie"));
+        assert!(!nested.contains("href=\"/about"));
+
+        assert!(out.join("_canopie/static/styles").is_dir());
+
+        fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn unknown_websites_are_an_error() {
+        let out = out_dir("unknown");
+
+        assert!(export_site(&fixture("site.json"), "no-such-site", &out, None).is_err());
+        assert!(!out.exists());
+    }
+}
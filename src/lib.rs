@@ -1,45 +1,170 @@
-use canopie_themes_atlas::ThemeAtlas;
-use canopie_themes_morningstar::ThemeMorningStar;
-use canopie_utils::themes::{GetThemeOverview, ThemeOverview, ThemeReference};
+use canopie_utils::themes::{ThemeOverview, ThemeReference};
 #[cfg(feature = "embed")]
-use canopie_utils::{renderer::ThemeRenderer, resource::embed::Resources};
+use canopie_utils::{
+    models::{Project, ProjectExtra},
+    renderer::ThemeRenderer,
+    resource::embed::Resources,
+};
+use serde_json::Value;
 
+mod access;
+mod cache;
 #[cfg(feature = "embed")]
-pub fn get_resources() -> Result<Resources, String> {
-    use canopie_themes_morningstar::ThemeMorningStar;
-    use canopie_utils::resource::embed::ThemeResource;
+mod export;
+#[cfg(feature = "utoipa")]
+mod openapi;
+mod registry;
+
+pub use canopie_themes_base::config::FieldError;
+
+pub use access::{get_themes_for_project, is_entitled, is_theme_available};
+pub use cache::RenderCache;
+#[cfg(feature = "embed")]
+pub use export::{ExportSummary, export_site};
+#[cfg(feature = "utoipa")]
+pub use openapi::ThemesApi;
+pub use registry::{RegisterThemes, ThemeEntry, ThemeRegistry, registry};
+
+/// Checks a stored theme config and reports every invalid field.
+pub fn validate_theme_config(theme_id: &str, value: &Value) -> Result<(), Vec<FieldError>> {
+    match registry()
+        .get(theme_id)
+        .and_then(|entry| entry.validate_config)
+    {
+        Some(validate_config) => validate_config(value),
+        None => Err(vec![FieldError::new(
+            "",
+            format!("unknown theme `{}`", theme_id),
+        )]),
+    }
+}
+
+/// Upgrades a stored theme config to the theme's current schema version.
+///
+/// The host should persist the returned value so migrations only run once.
+/// Returns `None` for unknown themes.
+pub fn upgrade_theme_config(theme_id: &str, value: Value) -> Option<Value> {
+    registry()
+        .get(theme_id)
+        .and_then(|entry| entry.upgrade_config)
+        .map(|upgrade_config| upgrade_config(value))
+}
 
+/// The default config of a theme, as the admin UI would store it.
+pub fn get_default_theme_config(theme_id: &str) -> Option<Value> {
+    registry()
+        .get(theme_id)
+        .and_then(|entry| entry.default_config)
+        .map(|default_config| default_config())
+}
+
+#[cfg(feature = "embed")]
+pub fn get_resources() -> Result<Resources, String> {
     let mut resources = Resources::new();
 
-    ThemeMorningStar::attach_theme_resources(&mut resources)?;
-    ThemeAtlas::attach_theme_resources(&mut resources)?;
+    for entry in registry().entries() {
+        if let Some(attach_theme_resources) = entry.resources {
+            attach_theme_resources(&mut resources)?;
+        }
+    }
 
     Ok(resources)
 }
 
+/// Every theme a website can pick, without the fallback theme.
 pub fn get_themes() -> Vec<ThemeReference> {
-    vec![
-        ThemeMorningStar::get_reference(),
-        ThemeAtlas::get_reference(),
-    ]
+    registry()
+        .entries()
+        .iter()
+        .filter(|entry| access::is_listed(entry))
+        .filter_map(|entry| entry.reference.map(|get_reference| get_reference()))
+        .collect()
 }
 
 pub fn get_theme_overview(theme_id: String) -> Option<ThemeOverview> {
-    match theme_id.as_str() {
-        "morningstar" => Some(ThemeMorningStar::get_theme_overview()),
-        "atlas" => Some(ThemeAtlas::get_theme_overview()),
-        _ => None,
+    registry()
+        .get(theme_id.as_str())
+        .and_then(|entry| entry.overview)
+        .map(|get_theme_overview| get_theme_overview())
+}
+
+/// Why [`get_theme`] rendered with the fallback theme instead of the requested one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeFallback {
+    UnknownTheme(String),
+    /// The theme exists but the website's project is not entitled to it.
+    NotEntitled(String),
+}
+
+/// The renderer chosen for a website, and whether the fallback theme was used.
+#[cfg(feature = "embed")]
+pub struct ThemeSelection {
+    pub renderer: ThemeRenderer,
+    pub fallback: Option<ThemeFallback>,
+}
+
+#[cfg(feature = "embed")]
+impl ThemeSelection {
+    pub fn used_fallback(&self) -> bool {
+        self.fallback.is_some()
     }
 }
 
 #[cfg(feature = "embed")]
-pub fn get_theme(theme_id: String) -> ThemeRenderer {
-    use canopie_utils::renderer::GetThemeRenderer;
+pub fn get_theme(theme_id: String) -> ThemeSelection {
+    select_theme(theme_id, |entry| entry.renderer)
+}
+
+/// Like [`get_theme`], but the renderer shows pages as CMS previews, with
+/// placeholders where content could not be displayed.
+#[cfg(feature = "embed")]
+pub fn get_theme_preview(theme_id: String) -> ThemeSelection {
+    select_theme(theme_id, |entry| entry.preview_renderer)
+}
+
+#[cfg(feature = "embed")]
+fn select_theme(
+    theme_id: String,
+    renderer: fn(&ThemeEntry) -> Option<fn() -> ThemeRenderer>,
+) -> ThemeSelection {
+    match registry().get(theme_id.as_str()).and_then(renderer) {
+        Some(get_theme_renderer) => ThemeSelection {
+            renderer: get_theme_renderer(),
+            fallback: None,
+        },
+        None => ThemeSelection {
+            renderer: fallback_renderer(renderer),
+            fallback: Some(ThemeFallback::UnknownTheme(theme_id)),
+        },
+    }
+}
 
-    match theme_id.as_str() {
-        "morningstar" => ThemeMorningStar::get_theme_renderer(),
-        "atlas" => ThemeAtlas::get_theme_renderer(),
-        // TODO: Create default theme
-        _ => ThemeMorningStar::get_theme_renderer(),
+/// Like [`get_theme`], but refuses restricted themes the project is not
+/// entitled to and renders the fallback theme instead.
+#[cfg(feature = "embed")]
+pub fn get_theme_for_project(
+    theme_id: String,
+    project: &Project,
+    extras: &[ProjectExtra],
+) -> ThemeSelection {
+    if registry().get(theme_id.as_str()).is_some()
+        && !is_theme_available(theme_id.as_str(), project, extras)
+    {
+        return ThemeSelection {
+            renderer: fallback_renderer(|entry| entry.renderer),
+            fallback: Some(ThemeFallback::NotEntitled(theme_id)),
+        };
     }
+
+    get_theme(theme_id)
+}
+
+#[cfg(feature = "embed")]
+fn fallback_renderer(renderer: fn(&ThemeEntry) -> Option<fn() -> ThemeRenderer>) -> ThemeRenderer {
+    let get_theme_renderer = registry()
+        .fallback()
+        .and_then(renderer)
+        .expect("the fallback theme must be registered with a renderer");
+
+    get_theme_renderer()
 }
@@ -1,6 +1,137 @@
-use canopie_themes::{get_theme_overview, get_themes};
+use std::{env, fs, process::ExitCode};
 
-fn main() -> () {
-    println!("{:?}", get_themes());
-    println!("{:?}", get_theme_overview("morningstar".to_string()));
+use canopie_themes::{
+    get_default_theme_config, get_theme_overview, get_themes, validate_theme_config,
+};
+use canopie_utils::themes::ThemeOverview;
+use serde::Serialize;
+use serde_json::Value;
+
+const USAGE: &str = "Usage: canopie-themes <command>
+
+Commands:
+  list                        List every bundled theme
+  overview <theme>            Print a theme's overview as JSON
+  schema <theme>              Print a theme's config schema as JSON
+  defaults <theme>            Print a theme's default config as JSON
+  validate <theme> <file>     Check a theme config JSON file against a theme
+  export <fixture> <website> <dir> [files]
+                              Export a website from a JSON fixture as static HTML,
+                              copying uploaded files from the files directory";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<(), String> {
+    match args {
+        ["list"] => print_json(&get_themes()),
+        ["overview", theme_id] => print_json(&overview(theme_id)?),
+        ["schema", theme_id] => print_json(&overview(theme_id)?.config),
+        ["defaults", theme_id] => {
+            let config =
+                get_default_theme_config(theme_id).ok_or_else(|| unknown_theme(theme_id))?;
+            print_json(&config)
+        }
+        ["validate", theme_id, file] => validate(theme_id, file),
+        ["export", fixture, website_id, out_dir] => export(fixture, website_id, out_dir, None),
+        ["export", fixture, website_id, out_dir, files_dir] => {
+            export(fixture, website_id, out_dir, Some(files_dir))
+        }
+        [] | ["help"] | ["-h"] | ["--help"] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(format!("Unknown command: {}\n\n{}", args.join(" "), USAGE)),
+    }
+}
+
+fn overview(theme_id: &str) -> Result<ThemeOverview, String> {
+    get_theme_overview(theme_id.to_string()).ok_or_else(|| unknown_theme(theme_id))
+}
+
+fn validate(theme_id: &str, file: &str) -> Result<(), String> {
+    let contents =
+        fs::read_to_string(file).map_err(|error| format!("Could not read {}: {}", file, error))?;
+    let config: Value = serde_json::from_str(&contents)
+        .map_err(|error| format!("{} is not valid JSON: {}", file, error))?;
+
+    match validate_theme_config(theme_id, &config) {
+        Ok(()) => {
+            println!("{} is a valid {} config", file, theme_id);
+            Ok(())
+        }
+        Err(errors) => {
+            for error in &errors {
+                if error.path.is_empty() {
+                    println!("{}", error.reason);
+                } else {
+                    println!("{}: {}", error.path, error.reason);
+                }
+            }
+            Err(format!("{} has {} invalid field(s)", file, errors.len()))
+        }
+    }
+}
+
+#[cfg(feature = "embed")]
+fn export(
+    fixture: &str,
+    website_id: &str,
+    out_dir: &str,
+    files_dir: Option<&str>,
+) -> Result<(), String> {
+    use std::path::Path;
+
+    use canopie_themes::export_site;
+    use canopie_themes_base::data::MemorySiteData;
+
+    let site = MemorySiteData::from_file(fixture)?;
+    let summary = export_site(
+        &site,
+        website_id,
+        Path::new(out_dir),
+        files_dir.map(Path::new),
+    )?;
+
+    for route in &summary.pages {
+        println!("wrote {}", route);
+    }
+    for route in &summary.skipped {
+        println!("skipped {} (not found)", route);
+    }
+    for file in &summary.files {
+        println!("copied {}", file);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "embed"))]
+fn export(
+    _fixture: &str,
+    _website_id: &str,
+    _out_dir: &str,
+    _files_dir: Option<&str>,
+) -> Result<(), String> {
+    Err("Exporting requires the embed feature".to_string())
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn unknown_theme(theme_id: &str) -> String {
+    format!("Unknown theme: {}", theme_id)
 }
@@ -0,0 +1,235 @@
+//! OpenAPI description of the theme listing endpoints.
+//!
+//! The theme types live in `canopie-utils`, so they are described here by
+//! mirror types that match their serialised shape. Each mirror converts from
+//! the type it describes by destructuring it, so a field added upstream fails
+//! to compile here, and the tests check real overviews against the generated
+//! spec. The API server merges [`ThemesApi`] into its own spec instead of
+//! hand-writing these paths.
+
+use canopie_utils::themes::{PageThemeOverview, ThemeOverview, ThemeReference, ThemeRestriction};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::{OpenApi, ToSchema};
+
+/// Who may pick a theme. Restricted themes list the keys that unlock them.
+#[derive(ToSchema, Serialize)]
+#[schema(as = ThemeRestriction)]
+enum ThemeRestrictionSchema {
+    None,
+    Restricted(Vec<String>),
+}
+
+/// A theme as listed by `get_themes`.
+#[derive(ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ThemeReference)]
+struct ThemeReferenceSchema {
+    id: String,
+    name: String,
+    description: Option<String>,
+}
+
+/// A page template offered by a theme, with its page-level config schema.
+#[derive(ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+#[schema(as = PageThemeOverview)]
+struct PageThemeOverviewSchema {
+    name: String,
+    #[schema(value_type = Vec<Object>)]
+    config: Vec<Value>,
+    default: bool,
+}
+
+/// A theme with its website-level config schema and page templates.
+#[derive(ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ThemeOverview)]
+struct ThemeOverviewSchema {
+    id: String,
+    name: String,
+    description: Option<String>,
+    restricted: ThemeRestrictionSchema,
+    #[schema(value_type = Vec<Object>)]
+    config: Vec<Value>,
+    page_themes: Vec<PageThemeOverviewSchema>,
+}
+
+impl From<ThemeRestriction> for ThemeRestrictionSchema {
+    fn from(restriction: ThemeRestriction) -> Self {
+        match restriction {
+            ThemeRestriction::None => Self::None,
+            ThemeRestriction::Restricted(keys) => Self::Restricted(keys),
+        }
+    }
+}
+
+impl From<ThemeReference> for ThemeReferenceSchema {
+    fn from(reference: ThemeReference) -> Self {
+        let ThemeReference {
+            id,
+            name,
+            description,
+        } = reference;
+
+        Self {
+            id,
+            name,
+            description,
+        }
+    }
+}
+
+impl From<PageThemeOverview> for PageThemeOverviewSchema {
+    fn from(page_theme: PageThemeOverview) -> Self {
+        let PageThemeOverview {
+            name,
+            config,
+            default,
+        } = page_theme;
+
+        Self {
+            name,
+            config: config.iter().map(to_value).collect(),
+            default,
+        }
+    }
+}
+
+impl From<ThemeOverview> for ThemeOverviewSchema {
+    fn from(overview: ThemeOverview) -> Self {
+        let ThemeOverview {
+            id,
+            name,
+            description,
+            restricted,
+            config,
+            page_themes,
+        } = overview;
+
+        Self {
+            id,
+            name,
+            description,
+            restricted: restricted.into(),
+            config: config.iter().map(to_value).collect(),
+            page_themes: page_themes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+fn to_value(property: &impl Serialize) -> Value {
+    serde_json::to_value(property).expect("config schema properties serialise to JSON")
+}
+
+#[utoipa::path(
+    get,
+    path = "/themes",
+    tag = "themes",
+    responses(
+        (status = 200, description = "Every theme bundled with the server", body = Vec<ThemeReferenceSchema>),
+    )
+)]
+fn list_themes() {}
+
+#[utoipa::path(
+    get,
+    path = "/themes/{theme_id}",
+    tag = "themes",
+    params(
+        ("theme_id" = String, Path, description = "Theme id, e.g. `atlas`"),
+    ),
+    responses(
+        (status = 200, description = "The theme's overview and config schema", body = ThemeOverviewSchema),
+        (status = 404, description = "No theme with that id"),
+    )
+)]
+fn get_theme_overview() {}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_themes, get_theme_overview),
+    components(schemas(
+        ThemeRestrictionSchema,
+        ThemeReferenceSchema,
+        PageThemeOverviewSchema,
+        ThemeOverviewSchema
+    )),
+    tags((name = "themes", description = "Website themes"))
+)]
+pub struct ThemesApi;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::registry::registry;
+
+    fn spec() -> Value {
+        serde_json::to_value(ThemesApi::openapi()).unwrap()
+    }
+
+    /// Checks `value` against the component `name` of `spec`. The spec's
+    /// components travel along with the schema so their `$ref`s resolve.
+    fn validate(spec: &Value, value: &Value, name: &str) -> Result<(), String> {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$ref": format!("#/components/schemas/{}", name),
+            "components": spec["components"],
+        });
+
+        jsonschema::validate(&schema, value).map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn every_theme_overview_matches_the_spec() {
+        let spec = spec();
+
+        for entry in registry().entries() {
+            let overview = serde_json::to_value((entry.overview.unwrap())()).unwrap();
+            let reference = serde_json::to_value((entry.reference.unwrap())()).unwrap();
+
+            if let Err(error) = validate(&spec, &overview, "ThemeOverview") {
+                panic!("{} overview: {}", entry.id, error);
+            }
+            if let Err(error) = validate(&spec, &reference, "ThemeReference") {
+                panic!("{} reference: {}", entry.id, error);
+            }
+        }
+    }
+
+    #[test]
+    fn mirror_types_serialise_like_the_types_they_describe() {
+        for entry in registry().entries() {
+            let overview = (entry.overview.unwrap())();
+            let expected = serde_json::to_value(&overview).unwrap();
+            let mirrored = serde_json::to_value(ThemeOverviewSchema::from(overview)).unwrap();
+            assert_eq!(mirrored, expected, "{} overview", entry.id);
+
+            let reference = (entry.reference.unwrap())();
+            let expected = serde_json::to_value(&reference).unwrap();
+            let mirrored = serde_json::to_value(ThemeReferenceSchema::from(reference)).unwrap();
+            assert_eq!(mirrored, expected, "{} reference", entry.id);
+        }
+    }
+
+    #[test]
+    fn validation_rejects_overviews_that_do_not_match() {
+        let spec = spec();
+        let overview = crate::get_theme_overview("atlas".to_string()).unwrap();
+        let overview = serde_json::to_value(overview).unwrap();
+
+        let mut missing = overview.clone();
+        missing.as_object_mut().unwrap().remove("page_themes");
+        assert!(validate(&spec, &missing, "ThemeOverview").is_err());
+
+        let mut unknown = overview.clone();
+        unknown["page_themes"][0]["layout"] = json!("grid");
+        assert!(validate(&spec, &unknown, "ThemeOverview").is_err());
+
+        let mut wrong_type = overview;
+        wrong_type["restricted"] = json!({ "Restricted": "atlas" });
+        assert!(validate(&spec, &wrong_type, "ThemeOverview").is_err());
+    }
+}
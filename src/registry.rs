@@ -0,0 +1,26 @@
+use std::sync::OnceLock;
+
+pub use canopie_themes_base::registry::{RegisterThemes, ThemeEntry, ThemeRegistry};
+
+/// The `register` function of every theme crate bundled with this crate.
+///
+/// Each crate decides which of its themes to register and with which
+/// capabilities; adding a theme crate only means adding it here.
+const THEME_CRATES: &[RegisterThemes] = &[
+    canopie_themes_morningstar::register,
+    canopie_themes_atlas::register,
+    canopie_themes_base::register,
+];
+
+/// The registry of themes bundled with this crate.
+pub fn registry() -> &'static ThemeRegistry {
+    static REGISTRY: OnceLock<ThemeRegistry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        THEME_CRATES
+            .iter()
+            .fold(ThemeRegistry::new(), |registry, register| {
+                register(registry)
+            })
+    })
+}
@@ -0,0 +1,118 @@
+use std::cell::Cell;
+
+use canopie_themes::{RenderCache, registry};
+use canopie_themes_base::{
+    content::RenderMode,
+    data::{
+        SiteData,
+        test_support::{UnversionedSiteData, WEBSITE_ID, fixture},
+    },
+};
+use canopie_utils::{header::Header, renderer::PageResult};
+
+fn render_counted(
+    cache: &RenderCache,
+    site: &dyn SiteData,
+    path: &str,
+    renders: &Cell<usize>,
+) -> String {
+    let website = site.website(WEBSITE_ID).unwrap();
+    let render_site = registry()
+        .get("base")
+        .and_then(|entry| entry.render_site)
+        .unwrap();
+
+    let mut headers = Header::default();
+    let result = cache.render(
+        "base",
+        site,
+        &mut headers,
+        website,
+        path,
+        |headers, website| {
+            renders.set(renders.get() + 1);
+            render_site(site, headers, website, path, RenderMode::Live).into()
+        },
+    );
+
+    match result {
+        PageResult::Found(markup) | PageResult::NotFound(markup) => markup.into_string(),
+    }
+}
+
+#[test]
+fn repeated_renders_are_served_from_the_cache() {
+    let cache = RenderCache::new(1024 * 1024);
+    let site = fixture("site.json");
+    let renders = Cell::new(0);
+
+    let first = render_counted(&cache, &site, "/", &renders);
+    let second = render_counted(&cache, &site, "/", &renders);
+
+    assert_eq!(first, second);
+    assert_eq!(renders.get(), 1);
+}
+
+#[test]
+fn content_updates_and_invalidation_render_again() {
+    let cache = RenderCache::new(1024 * 1024);
+    let mut site = fixture("site.json");
+    let renders = Cell::new(0);
+
+    render_counted(&cache, &site, "/", &renders);
+
+    site.page_contents[0].updated_at = "2026-01-01T00:00:00".parse().unwrap();
+    render_counted(&cache, &site, "/", &renders);
+    assert_eq!(renders.get(), 2);
+
+    cache.invalidate_website(WEBSITE_ID);
+    render_counted(&cache, &site, "/", &renders);
+    assert_eq!(renders.get(), 3);
+}
+
+#[test]
+fn cache_stays_within_its_budget() {
+    let cache = RenderCache::new(4 * 1024);
+    let site = fixture("site.json");
+    let renders = Cell::new(0);
+
+    for path in ["/", "/music", "/about/team", "/broken", "/does-not-exist"] {
+        render_counted(&cache, &site, path, &renders);
+    }
+
+    assert!(cache.size() <= 4 * 1024);
+}
+
+#[test]
+fn deleting_content_renders_again() {
+    let cache = RenderCache::new(1024 * 1024);
+    let mut site = fixture("site.json");
+    let renders = Cell::new(0);
+
+    render_counted(&cache, &site, "/", &renders);
+
+    // Every fixture row shares one timestamp, so the deletion leaves the
+    // newest updated_at where it was.
+    site.menu_items.retain(|item| item.id != "item-bandcamp");
+    render_counted(&cache, &site, "/", &renders);
+    assert_eq!(renders.get(), 2);
+
+    site.menu_item_labels.clear();
+    site.page_contents
+        .retain(|content| content.page_id != "page-broken");
+    render_counted(&cache, &site, "/", &renders);
+    assert_eq!(renders.get(), 3);
+}
+
+#[test]
+fn unknown_content_version_bypasses_the_cache() {
+    let cache = RenderCache::new(1024 * 1024);
+    let site = UnversionedSiteData(fixture("site.json"));
+    let renders = Cell::new(0);
+
+    render_counted(&cache, &site, "/", &renders);
+    render_counted(&cache, &site, "/", &renders);
+
+    assert_eq!(renders.get(), 2);
+    assert_eq!(cache.size(), 0);
+}
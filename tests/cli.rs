@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use serde_json::{Value, json};
+
+/// Writes `config` to a file named after the calling test, so tests running
+/// in parallel do not share one.
+fn config_file(name: &str, config: &Value) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "canopie-themes-cli-{}-{}.json",
+        std::process::id(),
+        name
+    ));
+    fs::write(&path, config.to_string()).unwrap();
+    path
+}
+
+fn validate(theme_id: &str, file: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_canopie-themes"))
+        .arg("validate")
+        .arg(theme_id)
+        .arg(file)
+        .output()
+        .expect("failed to run canopie-themes")
+}
+
+#[test]
+fn validate_accepts_a_valid_config() {
+    let file = config_file(
+        "valid",
+        &json!({ "site_title": "Field Notes", "accent_color": "#112233" }),
+    );
+
+    let output = validate("atlas", &file);
+    fs::remove_file(&file).unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("is a valid atlas config"));
+}
+
+#[test]
+fn validate_lists_invalid_fields_and_fails() {
+    let file = config_file(
+        "invalid",
+        &json!({ "accent_color": "blue", "layout_width": "extra-wide" }),
+    );
+
+    let output = validate("atlas", &file);
+    fs::remove_file(&file).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("accent_color: "), "stdout: {}", stdout);
+    assert!(stdout.contains("layout_width: "), "stdout: {}", stdout);
+    assert!(
+        stderr.contains("has 2 invalid field(s)"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn validate_fails_for_an_unknown_theme() {
+    let file = config_file("unknown-theme", &json!({}));
+
+    let output = validate("no-such-theme", &file);
+    fs::remove_file(&file).unwrap();
+
+    assert!(!output.status.success());
+}
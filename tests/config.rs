@@ -0,0 +1,165 @@
+use canopie_themes::{upgrade_theme_config, validate_theme_config};
+use canopie_themes_base::{
+    BaseThemeConfig,
+    config::{ConfigMigration, GetThemeConfig, upgrade_config},
+};
+use serde_json::{Value, json};
+
+/// A theme at config version 2, whose migrations record the order they ran in.
+struct MigratedTheme {}
+
+impl GetThemeConfig for MigratedTheme {
+    type Config = BaseThemeConfig;
+
+    fn migrations() -> Vec<ConfigMigration> {
+        vec![rename_text_colour, add_surface_colour]
+    }
+}
+
+fn rename_text_colour(value: Value) -> Value {
+    with_step(value, "rename text colour")
+}
+
+fn add_surface_colour(value: Value) -> Value {
+    with_step(value, "add surface colour")
+}
+
+fn with_step(mut value: Value, step: &str) -> Value {
+    if let Some(fields) = value.as_object_mut() {
+        fields
+            .entry("steps")
+            .or_insert_with(|| json!([]))
+            .as_array_mut()
+            .unwrap()
+            .push(json!(step));
+    }
+    value
+}
+
+#[test]
+fn valid_config_passes() {
+    let config = json!({ "site_title": "Field Notes", "accent_color": "#112233" });
+
+    assert_eq!(validate_theme_config("atlas", &config), Ok(()));
+}
+
+#[test]
+fn each_invalid_field_is_reported() {
+    let config = json!({
+        "site_title": "Field Notes",
+        "accent_color": "blue",
+        "muted_color": "#12",
+        "not_a_field": true,
+    });
+
+    let errors = validate_theme_config("atlas", &config).unwrap_err();
+    let mut paths: Vec<&str> = errors.iter().map(|error| error.path.as_str()).collect();
+    paths.sort();
+
+    assert_eq!(paths, vec!["accent_color", "muted_color", "not_a_field"]);
+}
+
+#[test]
+fn unknown_dropdown_value_is_reported() {
+    let config = json!({ "layout_width": "extra-wide", "header_style": "glass" });
+
+    let errors = validate_theme_config("atlas", &config).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "layout_width");
+}
+
+#[test]
+fn unknown_theme_is_an_error() {
+    assert!(validate_theme_config("no-such-theme", &json!({})).is_err());
+}
+
+#[test]
+fn upgraded_config_is_stamped_and_still_valid() {
+    let upgraded = upgrade_theme_config("atlas", json!({ "site_title": "Field Notes" })).unwrap();
+
+    assert!(upgraded.get("_version").is_some());
+    assert_eq!(upgraded["site_title"], "Field Notes");
+    assert_eq!(validate_theme_config("atlas", &upgraded), Ok(()));
+}
+
+#[test]
+fn migrations_run_in_order_from_the_stored_version() {
+    let unversioned = upgrade_config::<MigratedTheme>(json!({ "text_color": "#000000" }));
+
+    assert_eq!(
+        unversioned,
+        json!({
+            "text_color": "#000000",
+            "steps": ["rename text colour", "add surface colour"],
+            "_version": 2
+        })
+    );
+
+    let at_version_one = upgrade_config::<MigratedTheme>(json!({ "_version": 1 }));
+
+    assert_eq!(
+        at_version_one,
+        json!({ "steps": ["add surface colour"], "_version": 2 })
+    );
+}
+
+#[test]
+fn current_config_is_left_alone() {
+    let current = json!({ "text_color": "#000000", "_version": 2 });
+
+    assert_eq!(upgrade_config::<MigratedTheme>(current.clone()), current);
+}
+
+#[test]
+fn config_newer_than_the_theme_is_left_alone() {
+    let newer = json!({ "text_color": "#000000", "_version": 3 });
+
+    assert_eq!(upgrade_config::<MigratedTheme>(newer.clone()), newer);
+}
+
+#[test]
+fn unversioned_atlas_dropdown_values_are_lowercased() {
+    let upgraded = upgrade_theme_config(
+        "atlas",
+        json!({ "layout_width": " Wide ", "header_style": "SOLID" }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        upgraded,
+        json!({ "layout_width": "wide", "header_style": "solid", "_version": 1 })
+    );
+    assert_eq!(validate_theme_config("atlas", &upgraded), Ok(()));
+}
+
+#[test]
+fn unknown_atlas_dropdown_values_are_still_reported() {
+    let upgraded = upgrade_theme_config("atlas", json!({ "layout_width": "Huge" })).unwrap();
+
+    let errors = validate_theme_config("atlas", &upgraded).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "layout_width");
+}
+
+#[test]
+fn unversioned_morningstar_primary_color_becomes_the_accent() {
+    let upgraded =
+        upgrade_theme_config("morningstar", json!({ "primary_color": "#112233" })).unwrap();
+
+    assert_eq!(
+        upgraded,
+        json!({ "primary_color": "#112233", "accent_color": "#112233", "_version": 1 })
+    );
+}
+
+#[test]
+fn a_chosen_morningstar_accent_is_kept() {
+    let upgraded = upgrade_theme_config(
+        "morningstar",
+        json!({ "primary_color": "#112233", "accent_color": "#445566" }),
+    )
+    .unwrap();
+
+    assert_eq!(upgraded["accent_color"], "#445566");
+}
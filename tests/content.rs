@@ -0,0 +1,119 @@
+use canopie_themes::registry;
+use canopie_themes_base::{
+    content::{RenderMode, parse_content},
+    data::{
+        SiteData,
+        test_support::{WEBSITE_ID, fixture},
+    },
+    diagnostics::Diagnostics,
+    page::RenderOutcome,
+};
+use canopie_utils::{
+    components::{Formatter, build_components},
+    header::Header,
+};
+use serde_json::{Value, json};
+
+fn document() -> Value {
+    json!({
+        "type": "doc",
+        "content": [
+            {
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": "This paragraph is fine." }]
+            },
+            {
+                "type": "bulletList",
+                "content": [
+                    {
+                        "type": "listItem",
+                        "content": [{ "type": 42 }]
+                    }
+                ]
+            },
+            {
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": "So is this one." }]
+            }
+        ]
+    })
+}
+
+fn render(content: Value, preview: bool, diagnostics: &Diagnostics) -> String {
+    let node = parse_content("page-broken", content, preview, diagnostics);
+    let (markup, _formatter) = build_components(node, None, Some(Formatter::default(None)));
+    markup.into_string()
+}
+
+#[test]
+fn only_the_failing_node_is_skipped() {
+    let diagnostics = Diagnostics::new();
+    let html = render(document(), false, &diagnostics);
+
+    assert!(html.contains("This paragraph is fine."));
+    assert!(html.contains("So is this one."));
+    assert!(!html.contains("could not be displayed"));
+    assert!(!html.contains("Error parsing content"));
+}
+
+#[test]
+fn failing_node_is_reported_with_its_path() {
+    let diagnostics = Diagnostics::new();
+    render(document(), false, &diagnostics);
+
+    let entries = diagnostics.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].page_id.as_deref(), Some("page-broken"));
+    assert_eq!(
+        entries[0].node_path.as_deref(),
+        Some("$.content[1].content[0].content[0]")
+    );
+}
+
+#[test]
+fn preview_shows_a_placeholder() {
+    let diagnostics = Diagnostics::new();
+    let html = render(document(), true, &diagnostics);
+
+    assert!(html.contains("This paragraph is fine."));
+    assert!(html.contains("This block could not be displayed."));
+}
+
+#[test]
+fn valid_content_reports_nothing() {
+    let diagnostics = Diagnostics::new();
+    let content = json!({
+        "type": "doc",
+        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Hi" }] }]
+    });
+
+    render(content, false, &diagnostics);
+
+    assert!(diagnostics.is_empty());
+}
+
+/// Renders the fixture's `/broken` page, whose content has a failing node,
+/// through the base theme.
+fn render_broken_page(mode: RenderMode) -> String {
+    let site = fixture("site.json");
+    let website = site.website(WEBSITE_ID).unwrap();
+    let render_site = registry()
+        .get("base")
+        .and_then(|entry| entry.render_site)
+        .unwrap();
+
+    match render_site(&site, &mut Header::default(), website, "/broken", mode) {
+        RenderOutcome::Found(markup) => markup.into_string(),
+        _ => panic!("/broken was not found"),
+    }
+}
+
+#[test]
+fn the_caller_decides_whether_a_page_renders_as_a_preview() {
+    let live = render_broken_page(RenderMode::Live);
+    let preview = render_broken_page(RenderMode::Preview);
+
+    assert!(live.contains("This paragraph is fine."));
+    assert!(!live.contains("This block could not be displayed."));
+    assert!(preview.contains("This block could not be displayed."));
+}
@@ -0,0 +1,92 @@
+use canopie_themes::registry;
+use canopie_themes_base::{
+    content::RenderMode,
+    data::{
+        SiteData,
+        test_support::{CountingSiteData, TIMESTAMP, WEBSITE_ID, fixture, row},
+    },
+    page::RenderOutcome,
+};
+use canopie_utils::header::Header;
+use serde_json::json;
+
+/// Renders `path` of a fixture site with the Morningstar theme.
+fn render(name: &str, path: &str) -> RenderOutcome {
+    render_site_data(&fixture(name), path)
+}
+
+fn render_site_data<D: SiteData>(site: &D, path: &str) -> RenderOutcome {
+    let website = site.website(WEBSITE_ID).unwrap();
+    let render_site = registry()
+        .get("morningstar")
+        .and_then(|entry| entry.render_site)
+        .unwrap();
+
+    render_site(
+        site,
+        &mut Header::default(),
+        website,
+        path,
+        RenderMode::Live,
+    )
+}
+
+#[test]
+fn missing_pages_show_the_cms_not_found_content() {
+    let RenderOutcome::NotFound(body) = render("site.json", "/does-not-exist") else {
+        panic!("/does-not-exist was not answered with the 404 page");
+    };
+    assert!(
+        body.into_string()
+            .contains("This page is managed in the CMS.")
+    );
+}
+
+#[test]
+fn the_not_found_page_is_looked_up_by_path() {
+    let site = CountingSiteData::new(fixture("site.json"));
+
+    render_site_data(&site, "/does-not-exist");
+
+    let paths = site.paths.borrow();
+    assert!(paths.iter().any(|path| path == "/404"));
+    assert!(paths.iter().all(|path| path.starts_with('/')));
+}
+
+#[test]
+fn the_not_found_page_is_not_served_at_its_own_path() {
+    let RenderOutcome::NotFound(body) = render("site.json", "/404") else {
+        panic!("/404 was served as a page");
+    };
+    let body = body.into_string();
+    assert!(body.contains("Page not found"));
+    assert!(body.contains("This page is managed in the CMS."));
+}
+
+#[test]
+fn sites_without_a_main_menu_are_not_configured() {
+    let RenderOutcome::NotConfigured(body) = render("no_main_menu.json", "/") else {
+        panic!("a site without a Main menu was not reported as not configured");
+    };
+    let body = body.into_string();
+    assert!(body.contains("Coming soon"));
+    // What is missing is for the site's owner, not its visitors.
+    assert!(!body.contains("menu named"));
+}
+
+#[test]
+fn menu_item_labels_name_both_the_hero_link_and_the_section() {
+    let mut site = fixture("site.json");
+    site.menu_item_labels.push(row(json!({
+        "id": "label-music-en", "menu_item_id": "item-music", "language": "en",
+        "label": "Listen", "created_at": TIMESTAMP, "updated_at": TIMESTAMP
+    })));
+
+    let RenderOutcome::Found(body) = render_site_data(&site, "/") else {
+        panic!("/ was not found");
+    };
+    let body = body.into_string();
+    // The hero menu is rendered for wide and narrow screens, then the section.
+    assert_eq!(body.matches(r#"page-title="Listen""#).count(), 3);
+    assert!(!body.contains(r#"page-title="Music""#));
+}
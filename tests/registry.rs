@@ -0,0 +1,92 @@
+use canopie_themes::{ThemeRegistry, get_theme_overview, registry, validate_theme_config};
+use canopie_themes_atlas::ThemeAtlas;
+use canopie_themes_base::ThemeBase;
+use serde_json::json;
+
+#[test]
+fn bundled_themes_are_found_by_id() {
+    for theme_id in ["morningstar", "atlas", "base"] {
+        let entry = registry()
+            .get(theme_id)
+            .unwrap_or_else(|| panic!("{} is not registered", theme_id));
+
+        assert_eq!(entry.id, theme_id);
+        assert_eq!(
+            get_theme_overview(theme_id.to_string()).map(|overview| overview.id),
+            Some(theme_id.to_string())
+        );
+    }
+}
+
+#[test]
+fn unknown_ids_are_not_found() {
+    assert!(registry().get("no-such-theme").is_none());
+    assert!(get_theme_overview("no-such-theme".to_string()).is_none());
+    assert!(validate_theme_config("no-such-theme", &json!({})).is_err());
+}
+
+#[test]
+fn every_theme_crate_registers_every_capability() {
+    let incomplete = registry().incomplete();
+
+    assert!(
+        incomplete.is_empty(),
+        "themes registered without every capability: {:?}",
+        incomplete
+    );
+}
+
+#[test]
+fn base_is_the_fallback() {
+    let fallback = registry().fallback().expect("no fallback theme registered");
+
+    assert_eq!(fallback.id, "base");
+}
+
+#[test]
+fn fallback_must_be_registered_to_be_used() {
+    let registry = ThemeRegistry::new()
+        .register::<ThemeAtlas>()
+        .with_fallback::<ThemeBase>();
+
+    assert!(registry.fallback().is_none());
+}
+
+#[test]
+#[should_panic(expected = "theme `atlas` is registered twice")]
+fn registering_an_id_twice_panics() {
+    let _ = ThemeRegistry::new()
+        .register::<ThemeAtlas>()
+        .register::<ThemeAtlas>();
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn unknown_themes_render_with_the_fallback() {
+    use canopie_themes::{ThemeFallback, get_theme};
+
+    let selection = get_theme("no-such-theme".to_string());
+
+    assert_eq!(selection.renderer.name, "base");
+    assert_eq!(
+        selection.fallback,
+        Some(ThemeFallback::UnknownTheme("no-such-theme".to_string()))
+    );
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn previews_render_with_the_requested_theme_or_the_fallback() {
+    use canopie_themes::{ThemeFallback, get_theme_preview};
+
+    let selection = get_theme_preview("atlas".to_string());
+    assert_eq!(selection.renderer.name, "atlas");
+    assert!(!selection.used_fallback());
+
+    let selection = get_theme_preview("no-such-theme".to_string());
+    assert_eq!(selection.renderer.name, "base");
+    assert_eq!(
+        selection.fallback,
+        Some(ThemeFallback::UnknownTheme("no-such-theme".to_string()))
+    );
+}
@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+
+use canopie_themes_atlas::{AtlasThemeConfig, HeaderStyle, HeroLayout, LayoutWidth};
+use canopie_themes_base::schema::{
+    DropdownOptions, dropdowns_without_choices, with_dropdown_options,
+};
+use canopie_themes_morningstar::{
+    BorderIntensity, PageStylesheets, SectionStyle, ThemeMorningStar,
+};
+use canopie_utils::themes::{GetThemeOverview, PropertySchema, ThemeOverview, ThemeSchema};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
+
+/// The `value`s of the dropdown choices on `field`.
+fn choices(schema: &[PropertySchema], field: &str) -> Vec<String> {
+    let property = schema
+        .iter()
+        .find(|property| property.field == field)
+        .unwrap_or_else(|| panic!("no field {}", field));
+    let property = serde_json::to_value(property).unwrap();
+
+    property["meta"]["options"]["choices"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{} has no choices: {}", field, property))
+        .iter()
+        .map(|choice| choice["value"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn values<O: DropdownOptions>() -> Vec<String> {
+    O::options()
+        .into_iter()
+        .map(|(value, _text)| value.to_string())
+        .collect()
+}
+
+fn page_theme<'a>(overview: &'a ThemeOverview, name: &str) -> &'a [PropertySchema] {
+    &overview
+        .page_themes
+        .iter()
+        .find(|page_theme| page_theme.name == name)
+        .unwrap_or_else(|| panic!("no page theme {}", name))
+        .config
+}
+
+#[test]
+fn atlas_overview_lists_dropdown_choices() {
+    let overview = canopie_themes_atlas::get_theme_overview();
+
+    assert_eq!(
+        choices(&overview.config, "layout_width"),
+        values::<LayoutWidth>()
+    );
+    assert_eq!(
+        choices(&overview.config, "header_style"),
+        values::<HeaderStyle>()
+    );
+    assert_eq!(
+        choices(page_theme(&overview, "Atlas Page"), "hero_layout"),
+        values::<HeroLayout>()
+    );
+}
+
+#[test]
+fn morningstar_overview_lists_dropdown_choices() {
+    let overview = ThemeMorningStar::get_theme_overview();
+
+    assert_eq!(
+        choices(&overview.config, "border_intensity"),
+        values::<BorderIntensity>()
+    );
+    assert_eq!(
+        choices(page_theme(&overview, "Home Segment"), "section_style"),
+        values::<SectionStyle>()
+    );
+    assert_eq!(
+        choices(page_theme(&overview, "Full Page"), "stylesheet"),
+        values::<PageStylesheets>()
+    );
+}
+
+#[test]
+fn choices_are_not_empty() {
+    for values in [
+        values::<LayoutWidth>(),
+        values::<HeaderStyle>(),
+        values::<HeroLayout>(),
+        values::<BorderIntensity>(),
+        values::<SectionStyle>(),
+        values::<PageStylesheets>(),
+    ] {
+        assert!(values.len() > 1, "{:?}", values);
+    }
+}
+
+/// Every option value parses into `O` and serialises back unchanged, so
+/// what the admin UI stores is what the theme reads.
+fn assert_round_trips<O>()
+where
+    O: DropdownOptions + DeserializeOwned + Serialize + Debug,
+{
+    for (value, _text) in O::options() {
+        let parsed: O = serde_json::from_value(json!(value))
+            .unwrap_or_else(|error| panic!("{} does not parse: {}", value, error));
+
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            json!(value),
+            "{:?}",
+            parsed
+        );
+    }
+}
+
+#[test]
+fn option_values_round_trip_through_their_enums() {
+    assert_round_trips::<LayoutWidth>();
+    assert_round_trips::<HeaderStyle>();
+    assert_round_trips::<HeroLayout>();
+    assert_round_trips::<BorderIntensity>();
+    assert_round_trips::<SectionStyle>();
+}
+
+#[test]
+fn unknown_dropdown_fields_leave_the_schema_unchanged() {
+    let with_options =
+        with_dropdown_options::<LayoutWidth>(AtlasThemeConfig::schema(), "not_a_field");
+
+    assert_eq!(
+        serde_json::to_value(&with_options).unwrap(),
+        serde_json::to_value(AtlasThemeConfig::schema()).unwrap()
+    );
+}
+
+#[test]
+fn dropdowns_without_choices_are_reported() {
+    assert_eq!(
+        dropdowns_without_choices(&AtlasThemeConfig::schema()),
+        vec!["layout_width", "header_style"]
+    );
+    assert!(
+        dropdowns_without_choices(&canopie_themes_atlas::get_theme_overview().config).is_empty()
+    );
+}
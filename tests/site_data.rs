@@ -0,0 +1,117 @@
+use canopie_themes::registry;
+use canopie_themes_base::{
+    content::RenderMode,
+    data::{
+        SiteData, select_content,
+        test_support::{CountingSiteData, WEBSITE_ID, fixture},
+    },
+};
+use canopie_utils::{header::Header, models::PageContent};
+use serde_json::json;
+
+#[test]
+fn menu_pages_are_loaded_in_one_batch() {
+    for entry in registry().entries() {
+        let render_site = entry.render_site.unwrap();
+
+        for path in ["/", "/music", "/about/team", "/does-not-exist"] {
+            let site = CountingSiteData::new(fixture("site.json"));
+            let website = site.website(WEBSITE_ID).unwrap();
+
+            render_site(
+                &site,
+                &mut Header::default(),
+                website,
+                path,
+                RenderMode::Live,
+            );
+
+            assert_eq!(
+                site.pages_by_ids.get(),
+                1,
+                "{} {}: pages_by_ids calls",
+                entry.id,
+                path
+            );
+            assert_eq!(
+                site.page_from_id.get(),
+                0,
+                "{} {}: page_from_id calls",
+                entry.id,
+                path
+            );
+        }
+    }
+}
+
+fn content(id: u8, default_content: bool, preview: bool, updated_at: &str) -> PageContent {
+    serde_json::from_value(json!({
+        "id": format!("00000000-0000-0000-0000-0000000000{:02}", id),
+        "page_id": "page-home",
+        "default_content": default_content,
+        "preview": preview,
+        "language": "en",
+        "content": { "type": "doc", "content": [] },
+        "created_at": "2025-01-01T00:00:00",
+        "updated_at": updated_at,
+        "template": null
+    }))
+    .unwrap()
+}
+
+fn selected_id(contents: Vec<PageContent>) -> Option<String> {
+    select_content(contents).map(|content| content.id.to_string())
+}
+
+#[test]
+fn published_default_content_is_selected_first() {
+    let selected = selected_id(vec![
+        content(1, false, false, "2025-03-01T00:00:00"),
+        content(2, true, false, "2025-01-01T00:00:00"),
+        content(3, true, true, "2025-04-01T00:00:00"),
+    ]);
+
+    assert_eq!(
+        selected.as_deref(),
+        Some("00000000-0000-0000-0000-000000000002")
+    );
+}
+
+#[test]
+fn newest_published_content_is_selected_without_a_default() {
+    let selected = selected_id(vec![
+        content(1, false, false, "2025-01-01T00:00:00"),
+        content(2, false, false, "2025-03-01T00:00:00"),
+        content(3, false, true, "2025-04-01T00:00:00"),
+    ]);
+
+    assert_eq!(
+        selected.as_deref(),
+        Some("00000000-0000-0000-0000-000000000002")
+    );
+}
+
+#[test]
+fn preview_content_is_never_selected() {
+    assert_eq!(
+        selected_id(vec![content(1, true, true, "2025-01-01T00:00:00")]),
+        None
+    );
+}
+
+#[test]
+fn every_lookup_selects_the_same_content() {
+    let site = fixture("site.json");
+
+    let by_path = site.page(WEBSITE_ID, "/music").unwrap();
+    let by_id = site.page_from_id("page-music").unwrap();
+    let by_ids = site
+        .pages_by_ids(&["page-music".to_string()])
+        .pop()
+        .unwrap();
+
+    let content_id =
+        |(_page, content): &PageWithContent| content.as_ref().map(|content| content.id);
+    assert_eq!(content_id(&by_path), content_id(&by_id));
+    assert_eq!(content_id(&by_id), content_id(&by_ids));
+}
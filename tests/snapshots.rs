@@ -0,0 +1,152 @@
+//! Golden snapshots of every registered theme rendered against fixture sites.
+//!
+//! Each theme renders every case below, and the status, collected headers
+//! (title and assets) and body HTML are compared with
+//! `tests/snapshots/<theme>/<case>.snap`.
+//!
+//! After an intended change, regenerate the snapshots with
+//! `UPDATE_SNAPSHOTS=1 cargo test --test snapshots` and review the diff.
+//! A missing snapshot is written on the first run, so new themes and cases
+//! record theirs locally and the files can be committed. Under `CI` a
+//! missing snapshot fails the test like a mismatched one.
+
+use std::{env, fs, path::PathBuf};
+
+use canopie_themes::registry;
+use canopie_themes_base::{
+    content::RenderMode,
+    data::{
+        SiteData, SiteRenderer,
+        test_support::{WEBSITE_ID, fixture},
+    },
+    page::RenderOutcome,
+};
+use canopie_utils::header::Header;
+
+struct Case {
+    name: &'static str,
+    fixture: &'static str,
+    path: &'static str,
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "home",
+        fixture: "site.json",
+        path: "/",
+    },
+    Case {
+        name: "nested_page",
+        fixture: "site.json",
+        path: "/about/team",
+    },
+    Case {
+        name: "parent_page",
+        fixture: "site.json",
+        path: "/about",
+    },
+    Case {
+        name: "missing_page",
+        fixture: "site.json",
+        path: "/does-not-exist",
+    },
+    Case {
+        name: "not_found_page",
+        fixture: "site.json",
+        path: "/404",
+    },
+    Case {
+        name: "broken_content",
+        fixture: "site.json",
+        path: "/broken",
+    },
+    Case {
+        name: "inactive_page",
+        fixture: "site.json",
+        path: "/draft",
+    },
+    Case {
+        name: "inactive_menu_item",
+        fixture: "site.json",
+        path: "/hidden",
+    },
+    Case {
+        name: "no_main_menu",
+        fixture: "no_main_menu.json",
+        path: "/",
+    },
+];
+
+fn tests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn render_case(render_site: SiteRenderer, case: &Case) -> String {
+    let site = fixture(case.fixture);
+    let website = site
+        .website(WEBSITE_ID)
+        .expect("fixture is missing its website");
+
+    let mut headers = Header::default();
+    let (status, body) =
+        match render_site(&site, &mut headers, website, case.path, RenderMode::Live) {
+            RenderOutcome::Found(markup) => ("found", markup.into_string()),
+            RenderOutcome::NotFound(markup) => ("not found", markup.into_string()),
+            RenderOutcome::NotConfigured(markup) => ("not configured", markup.into_string()),
+        };
+
+    format!(
+        "path: {}\nstatus: {}\n--- headers\n{:#?}\n--- body\n{}\n",
+        case.path,
+        status,
+        headers,
+        body.replace("><", ">\n<")
+    )
+}
+
+#[test]
+fn themes_match_snapshots() {
+    let update = env::var("UPDATE_SNAPSHOTS").is_ok_and(|value| value != "0");
+    let ci = env::var("CI").is_ok_and(|value| value != "false" && value != "0");
+    let mut missing = vec![];
+    let mut mismatches = vec![];
+
+    for entry in registry().entries() {
+        let render_site = entry
+            .render_site
+            .unwrap_or_else(|| panic!("{} has no site renderer", entry.id));
+
+        for case in CASES {
+            let rendered = render_case(render_site, case);
+            let snapshot_path = tests_dir()
+                .join("snapshots")
+                .join(&entry.id)
+                .join(format!("{}.snap", case.name));
+
+            let expected = fs::read_to_string(&snapshot_path).ok();
+            if expected.as_deref() == Some(rendered.as_str()) {
+                continue;
+            }
+
+            if update || (expected.is_none() && !ci) {
+                fs::create_dir_all(snapshot_path.parent().unwrap()).unwrap();
+                fs::write(&snapshot_path, rendered).unwrap();
+                if expected.is_none() {
+                    eprintln!("recorded new snapshot {}", snapshot_path.display());
+                }
+            } else if expected.is_none() {
+                missing.push(snapshot_path.display().to_string());
+            } else {
+                mismatches.push(snapshot_path.display().to_string());
+            }
+        }
+    }
+
+    assert!(
+        missing.is_empty() && mismatches.is_empty(),
+        "rendered output differs from snapshots (rerun with UPDATE_SNAPSHOTS=1 to accept)\n\
+         missing:\n{}\nmismatched:\n{}",
+        missing.join("\n"),
+        mismatches.join("\n")
+    );
+}